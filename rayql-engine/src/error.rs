@@ -1,5 +1,5 @@
 use rayql::{
-    schema::error::{ParseError, TokenizationError},
+    schema::error::{LoadError, ParseError, TokenizationError},
     sql::error::{FunctionError, ToSQLError},
 };
 
@@ -34,8 +34,44 @@ pub fn pretty_error_message(error: &ParseError, code: &str) -> String {
             identifier, line_number, column
         ),
         ParseError::UnexpectedEndOfTokens => "Unexpected end of tokens".to_string(),
-        ParseError::FieldAlreadyExistsOnModel { field, model, line_number, column } => format!(
-            "Field '{field}' already exists on model '{model}', cannot redeclare it at line {}, column {}.",
+        ParseError::FieldAlreadyExistsOnModel { field, model, line_number, column, other_line_number, other_column, other_file } => format!(
+            "Field '{field}' already exists on model '{model}', cannot redeclare it at line {}, column {} (also declared at line {}, column {}{}).",
+            line_number, column, other_line_number, other_column,
+            other_file.as_ref().map_or(String::new(), |file| format!(" of '{}'", file)),
+        ),
+        ParseError::FieldAlreadyInConstraint { field, model, line_number, column, other_line_number, other_column } => format!(
+            "Field '{field}' is listed more than once in a constraint of model '{model}', repeated at line {}, column {} (first listed at line {}, column {}).",
+            line_number, column, other_line_number, other_column,
+        ),
+        ParseError::MixinFieldAlreadyExistsOnModel(clash) => format!(
+            "Field '{}' already exists on model '{}' (declared at line {}, column {}{}), mixin '{}' used at line {}, column {} declares it again at line {}, column {}{}.",
+            clash.field, clash.model, clash.other_line_number, clash.other_column,
+            clash.other_file.as_ref().map_or(String::new(), |file| format!(" of '{}'", file)),
+            clash.mixin, clash.line_number, clash.column, clash.mixin_line_number, clash.mixin_column,
+            clash.mixin_file.as_ref().map_or(String::new(), |file| format!(" of '{}'", file)),
+        ),
+        ParseError::InvalidDataType { data_type, reason, line_number, column } => format!(
+            "Invalid data type '{data_type}' at line {}, column {}: {}",
+            line_number, column, reason
+        ),
+        ParseError::ModelNotFound { model, line_number, column } => format!(
+            "Model '{model}' is not declared, used at line {}, column {}",
+            line_number, column
+        ),
+        ParseError::InheritanceCycle { model, line_number, column } => format!(
+            "Model '{model}' cannot extend itself, directly or through its parents, at line {}, column {}",
+            line_number, column
+        ),
+        ParseError::MixinNotFound { mixin, line_number, column } => format!(
+            "Mixin '{mixin}' is not declared, used at line {}, column {}",
+            line_number, column
+        ),
+        ParseError::InvalidEnumVariantValue { r#enum, variant, reason, line_number, column } => format!(
+            "Invalid value for variant '{variant}' of enum '{enum}': {reason} at line {}, column {}.",
+            line_number, column,
+        ),
+        ParseError::ColumnAlreadyExistsOnView { name, view, line_number, column } => format!(
+            "Column '{name}' already exists on view '{view}', cannot redeclare it at line {}, column {}.",
             line_number, column,
         ),
         ParseError::EnumVariantAlreadyExists { r#enum, variant, line_number, column } => format!(
@@ -45,6 +81,15 @@ pub fn pretty_error_message(error: &ParseError, code: &str) -> String {
     }
 }
 
+pub fn pretty_load_error_message(error: &LoadError) -> String {
+    match error {
+        LoadError::ParseError { path, code, source } => {
+            format!("{}: {}", path, pretty_error_message(source, code))
+        }
+        e => e.to_string(),
+    }
+}
+
 fn pretty_tokenization_error_message(
     tokenization_error: &TokenizationError,
     _code: &str,
@@ -79,6 +124,9 @@ pub fn pretty_to_sql_error_message(error: ToSQLError, code: &str) -> String {
             line_number,
             column,
         } => pretty_function_error_message(source, code, line_number, column),
+        ToSQLError::InFile { file, source } => {
+            format!("{}: {}", file, pretty_to_sql_error_message(*source, code))
+        }
         e => e.to_string(),
     }
 }
@@ -112,5 +160,11 @@ fn pretty_function_error_message(
                 line_number, column
             )
         }
+        FunctionError::UnknownNamedArgument(func, argument) => {
+            format!(
+                "Unknown argument '{argument}' passed to '{func}' at line {}, column {}",
+                line_number, column
+            )
+        }
     }
 }
@@ -48,6 +48,46 @@ pub enum ParseError {
         model: String,
         line_number: usize,
         column: usize,
+        other_line_number: usize,
+        other_column: usize,
+        // Set when the other declaration comes from another file.
+        other_file: Option<String>,
+    },
+    #[error("Field '{field}' is listed more than once in a constraint of model '{model}'")]
+    FieldAlreadyInConstraint {
+        field: String,
+        model: String,
+        line_number: usize,
+        column: usize,
+        other_line_number: usize,
+        other_column: usize,
+    },
+    #[error("Field '{}' of mixin '{}' already exists on model '{}'", .0.field, .0.mixin, .0.model)]
+    MixinFieldAlreadyExistsOnModel(Box<MixinFieldClash>),
+    #[error("Invalid data type '{data_type}': {reason}")]
+    InvalidDataType {
+        data_type: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    #[error("Model '{model}' is not declared")]
+    ModelNotFound {
+        model: String,
+        line_number: usize,
+        column: usize,
+    },
+    #[error("Model '{model}' extends itself")]
+    InheritanceCycle {
+        model: String,
+        line_number: usize,
+        column: usize,
+    },
+    #[error("Mixin '{mixin}' is not declared")]
+    MixinNotFound {
+        mixin: String,
+        line_number: usize,
+        column: usize,
     },
     #[error("Variant '{variant}' already exists on enum '{r#enum}'")]
     EnumVariantAlreadyExists {
@@ -56,6 +96,21 @@ pub enum ParseError {
         line_number: usize,
         column: usize,
     },
+    #[error("Column with name '{name}' already exists on view '{view}'")]
+    ColumnAlreadyExistsOnView {
+        name: String,
+        view: String,
+        line_number: usize,
+        column: usize,
+    },
+    #[error("Invalid value for variant '{variant}' of enum '{r#enum}': {reason}")]
+    InvalidEnumVariantValue {
+        variant: String,
+        r#enum: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
     #[error("Invalid reference, cannot access '{entity}' of '{property}'")]
     InvalidReference {
         entity: String,
@@ -66,3 +121,45 @@ pub enum ParseError {
     #[error("Unexpected End of Tokens")]
     UnexpectedEndOfTokens,
 }
+
+// A field spliced by `use` which is already declared on the model, boxed to
+// keep `ParseError` small.
+#[derive(Debug, PartialEq)]
+pub struct MixinFieldClash {
+    pub field: String,
+    pub mixin: String,
+    pub model: String,
+    // Position of the `use`.
+    pub line_number: usize,
+    pub column: usize,
+    pub mixin_line_number: usize,
+    pub mixin_column: usize,
+    // Set when the mixin is declared in another file than the model.
+    pub mixin_file: Option<String>,
+    pub other_line_number: usize,
+    pub other_column: usize,
+    // Set when the other declaration comes from another file.
+    pub other_file: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoadError {
+    #[error("Could not read '{path}': {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Parse Error in '{path}': {source}")]
+    ParseError {
+        path: String,
+        code: String,
+        source: Box<ParseError>,
+    },
+    #[error("Import cycle, '{import}' is already being imported, at line {line_number}, column {column} of '{path}'")]
+    ImportCycle {
+        path: String,
+        import: String,
+        line_number: usize,
+        column: usize,
+    },
+}
@@ -0,0 +1,472 @@
+use rayql::{
+    schema::{Field, Model, Property, Reference, Schema},
+    sql::error::ToSQLError,
+    types::DataType,
+    Value,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UnaryOperator {
+    Not,
+    Negate,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BinaryOperator {
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Concat,
+}
+
+impl BinaryOperator {
+    pub fn from_operator(operator: &str) -> Option<BinaryOperator> {
+        match operator {
+            "=" | "==" => Some(BinaryOperator::Equal),
+            "!=" | "<>" => Some(BinaryOperator::NotEqual),
+            "<" => Some(BinaryOperator::LessThan),
+            "<=" => Some(BinaryOperator::LessThanOrEqual),
+            ">" => Some(BinaryOperator::GreaterThan),
+            ">=" => Some(BinaryOperator::GreaterThanOrEqual),
+            "+" => Some(BinaryOperator::Add),
+            "-" => Some(BinaryOperator::Subtract),
+            "*" => Some(BinaryOperator::Multiply),
+            "/" => Some(BinaryOperator::Divide),
+            "%" => Some(BinaryOperator::Modulo),
+            "||" => Some(BinaryOperator::Concat),
+            _ => None,
+        }
+    }
+
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 1,
+            BinaryOperator::And => 2,
+            BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => 4,
+            BinaryOperator::Add | BinaryOperator::Subtract => 5,
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 6,
+            BinaryOperator::Concat => 7,
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        self.precedence() == 4
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExpressionKind {
+    Value(Value),
+    Null,
+    Field(String),
+    Reference(Reference),
+    FunctionCall(String, Vec<Expression>),
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(Box<Expression>, BinaryOperator, Box<Expression>),
+    In {
+        expression: Box<Expression>,
+        list: Vec<Expression>,
+        negated: bool,
+    },
+    Like {
+        expression: Box<Expression>,
+        pattern: Box<Expression>,
+        negated: bool,
+    },
+    IsNull {
+        expression: Box<Expression>,
+        negated: bool,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Expression {
+    pub fn new(kind: ExpressionKind, line_number: usize, column: usize) -> Self {
+        Expression {
+            kind,
+            line_number,
+            column,
+        }
+    }
+
+    pub fn precedence(&self) -> u8 {
+        match &self.kind {
+            ExpressionKind::Binary(_, operator, _) => operator.precedence(),
+            ExpressionKind::Unary(UnaryOperator::Not, _) => 3,
+            ExpressionKind::In { .. }
+            | ExpressionKind::Like { .. }
+            | ExpressionKind::IsNull { .. } => 4,
+            _ => 8,
+        }
+    }
+
+    // Names of the model fields the expression reads.
+    pub fn fields(&self) -> Vec<&str> {
+        let mut fields = Vec::new();
+        self.collect_fields(&mut fields);
+        fields
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match &self.kind {
+            ExpressionKind::Field(name) => fields.push(name),
+            ExpressionKind::FunctionCall(_, arguments) => {
+                arguments.iter().for_each(|arg| arg.collect_fields(fields))
+            }
+            ExpressionKind::Unary(_, operand) => operand.collect_fields(fields),
+            ExpressionKind::Binary(left, _, right) => {
+                left.collect_fields(fields);
+                right.collect_fields(fields);
+            }
+            ExpressionKind::In {
+                expression, list, ..
+            } => {
+                expression.collect_fields(fields);
+                list.iter().for_each(|item| item.collect_fields(fields));
+            }
+            ExpressionKind::Like {
+                expression,
+                pattern,
+                ..
+            } => {
+                expression.collect_fields(fields);
+                pattern.collect_fields(fields);
+            }
+            ExpressionKind::IsNull { expression, .. } => expression.collect_fields(fields),
+            ExpressionKind::Value(_) | ExpressionKind::Null | ExpressionKind::Reference(_) => {}
+        }
+    }
+
+    fn invalid(&self, reason: String) -> ToSQLError {
+        ToSQLError::InvalidExpression {
+            reason,
+            line_number: self.line_number,
+            column: self.column,
+        }
+    }
+}
+
+// SQLite functions which may be called from expressions, with their minimum
+// and maximum number of arguments.
+const FUNCTIONS: &[(&str, usize, usize)] = &[
+    ("abs", 1, 1),
+    ("coalesce", 2, usize::MAX),
+    ("date", 1, usize::MAX),
+    ("datetime", 1, usize::MAX),
+    ("ifnull", 2, 2),
+    ("instr", 2, 2),
+    ("json_valid", 1, 1),
+    ("julianday", 1, usize::MAX),
+    ("length", 1, 1),
+    ("lower", 1, 1),
+    ("ltrim", 1, 2),
+    ("replace", 3, 3),
+    ("round", 1, 2),
+    ("rtrim", 1, 2),
+    ("strftime", 2, usize::MAX),
+    ("substr", 2, 3),
+    ("time", 1, usize::MAX),
+    ("trim", 1, 2),
+    ("typeof", 1, 1),
+    ("unixepoch", 1, usize::MAX),
+    ("upper", 1, 1),
+];
+
+fn is_numeric(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Integer | DataType::Real | DataType::Boolean | DataType::Decimal { .. }
+    )
+}
+
+fn is_textual(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::String
+            | DataType::Timestamp
+            | DataType::Json(_)
+            | DataType::Date
+            | DataType::Time
+            | DataType::Uuid
+    )
+}
+
+// `None` stands for `null`, which is comparable to anything.
+fn is_comparable(left: &Option<DataType>, right: &Option<DataType>) -> bool {
+    match (left, right) {
+        (Some(left), Some(right)) => {
+            left.eq(right)
+                || (is_numeric(left) && is_numeric(right))
+                || (is_textual(left) && is_textual(right))
+        }
+        _ => true,
+    }
+}
+
+// Enums are typed as the values stored in their column, the type of a field
+// ignores whether it is optional.
+fn column_type(data_type: &DataType, schema: &Schema) -> DataType {
+    match data_type.without_optional() {
+        DataType::Enum(name) => schema
+            .get_enum(name)
+            .map_or(DataType::String, |e| e.data_type.clone()),
+        data_type => data_type.clone(),
+    }
+}
+
+impl Model {
+    // Type checks an expression against the fields of the model and returns
+    // its type, `None` meaning the expression is always `null`.
+    pub fn check_expression(
+        &self,
+        expression: &Expression,
+        schema: &Schema,
+    ) -> Result<Option<DataType>, ToSQLError> {
+        match &expression.kind {
+            ExpressionKind::Value(value) => Ok(Some(value.get_type())),
+            ExpressionKind::Null => Ok(None),
+            ExpressionKind::Field(name) => match self.get_field(name) {
+                Some(field) if !field.is_relation() => {
+                    Ok(Some(column_type(&field.data_type.data_type, schema)))
+                }
+                _ => Err(ToSQLError::FieldNotFound {
+                    model_name: self.name.clone(),
+                    field_name: name.clone(),
+                    line_number: expression.line_number,
+                    column: expression.column,
+                }),
+            },
+            ExpressionKind::Reference(reference) => {
+                reference.variant_reference_to_sql(schema)?;
+                Ok(Some(column_type(
+                    &DataType::Enum(reference.entity.clone()),
+                    schema,
+                )))
+            }
+            ExpressionKind::FunctionCall(name, arguments) => {
+                self.check_function_call(expression, name, arguments, schema)
+            }
+            ExpressionKind::Unary(UnaryOperator::Not, operand) => {
+                self.expect_boolean(operand, schema)?;
+                Ok(Some(DataType::Boolean))
+            }
+            ExpressionKind::Unary(UnaryOperator::Negate, operand) => {
+                self.expect_numeric(operand, schema)
+            }
+            ExpressionKind::Binary(left, operator, right) => match operator {
+                BinaryOperator::And | BinaryOperator::Or => {
+                    self.expect_boolean(left, schema)?;
+                    self.expect_boolean(right, schema)?;
+                    Ok(Some(DataType::Boolean))
+                }
+                BinaryOperator::Concat => {
+                    self.check_expression(left, schema)?;
+                    self.check_expression(right, schema)?;
+                    Ok(Some(DataType::String))
+                }
+                operator if operator.is_comparison() => {
+                    let left_type = self.check_expression(left, schema)?;
+                    let right_type = self.check_expression(right, schema)?;
+                    self.expect_comparable(right, &left_type, &right_type)?;
+                    Ok(Some(DataType::Boolean))
+                }
+                _ => {
+                    let left_type = self.expect_numeric(left, schema)?;
+                    let right_type = self.expect_numeric(right, schema)?;
+                    Ok(match (left_type, right_type) {
+                        (Some(DataType::Real | DataType::Decimal { .. }), _)
+                        | (_, Some(DataType::Real | DataType::Decimal { .. })) => {
+                            Some(DataType::Real)
+                        }
+                        (None, _) | (_, None) => None,
+                        _ => Some(DataType::Integer),
+                    })
+                }
+            },
+            ExpressionKind::In {
+                expression: left,
+                list,
+                ..
+            } => {
+                let left_type = self.check_expression(left, schema)?;
+                for item in list {
+                    let item_type = self.check_expression(item, schema)?;
+                    self.expect_comparable(item, &left_type, &item_type)?;
+                }
+                Ok(Some(DataType::Boolean))
+            }
+            ExpressionKind::Like {
+                expression: left,
+                pattern,
+                ..
+            } => {
+                self.expect_textual(left, schema)?;
+                self.expect_textual(pattern, schema)?;
+                Ok(Some(DataType::Boolean))
+            }
+            ExpressionKind::IsNull {
+                expression: left, ..
+            } => {
+                self.check_expression(left, schema)?;
+                Ok(Some(DataType::Boolean))
+            }
+        }
+    }
+
+    // Type checks the argument of a `check(...)` constraint.
+    pub fn check_condition(
+        &self,
+        expression: &Expression,
+        schema: &Schema,
+    ) -> Result<(), ToSQLError> {
+        self.expect_boolean(expression, schema)
+    }
+
+    // Validates a `generated(...)` column, its expression can only read the
+    // other fields of the model.
+    pub fn check_generated_column(
+        &self,
+        field: &Field,
+        expression: &Expression,
+        schema: &Schema,
+    ) -> Result<(), ToSQLError> {
+        let invalid_property = |reason: &str| ToSQLError::InvalidProperty {
+            model_name: self.name.clone(),
+            field_name: field.name.clone(),
+            reason: reason.to_string(),
+            line_number: expression.line_number,
+            column: expression.column,
+        };
+
+        if field.properties.contains(&Property::PrimaryKey) {
+            return Err(invalid_property(
+                "a generated column cannot be a primary key",
+            ));
+        }
+
+        if field.has_function_call("default") {
+            return Err(invalid_property("a generated column cannot have a default"));
+        }
+
+        if expression.fields().contains(&field.name.as_str()) {
+            return Err(invalid_property(
+                "a generated column cannot reference itself",
+            ));
+        }
+
+        let field_type = column_type(&field.data_type.data_type, schema);
+        let expression_type = self.check_expression(expression, schema)?;
+        self.expect_comparable(expression, &Some(field_type), &expression_type)
+    }
+
+    fn check_function_call(
+        &self,
+        expression: &Expression,
+        name: &str,
+        arguments: &[Expression],
+        schema: &Schema,
+    ) -> Result<Option<DataType>, ToSQLError> {
+        let (min, max) = match FUNCTIONS.iter().find(|(function, _, _)| function.eq(&name)) {
+            Some((_, min, max)) => (*min, *max),
+            None => {
+                return Err(expression
+                    .invalid(format!("function '{}' cannot be used in expressions", name)))
+            }
+        };
+
+        if arguments.len() < min || arguments.len() > max {
+            return Err(expression.invalid(format!(
+                "function '{}' called with {} arguments",
+                name,
+                arguments.len()
+            )));
+        }
+
+        let mut argument_types = Vec::new();
+        for argument in arguments {
+            argument_types.push(self.check_expression(argument, schema)?);
+        }
+
+        Ok(match name {
+            "abs" => self.expect_numeric(&arguments[0], schema)?,
+            "length" | "instr" | "unixepoch" => Some(DataType::Integer),
+            "round" | "julianday" => Some(DataType::Real),
+            "json_valid" => Some(DataType::Boolean),
+            "coalesce" | "ifnull" => argument_types.into_iter().flatten().next(),
+            "lower" | "upper" | "trim" | "ltrim" | "rtrim" | "substr" | "replace" => {
+                self.expect_textual(&arguments[0], schema)?;
+                Some(DataType::String)
+            }
+            _ => Some(DataType::String),
+        })
+    }
+
+    fn expect_boolean(&self, expression: &Expression, schema: &Schema) -> Result<(), ToSQLError> {
+        match self.check_expression(expression, schema)? {
+            Some(DataType::Boolean) | None => Ok(()),
+            Some(data_type) => {
+                Err(expression.invalid(format!("expected a condition, got {}", data_type)))
+            }
+        }
+    }
+
+    fn expect_numeric(
+        &self,
+        expression: &Expression,
+        schema: &Schema,
+    ) -> Result<Option<DataType>, ToSQLError> {
+        match self.check_expression(expression, schema)? {
+            Some(data_type) if !is_numeric(&data_type) => {
+                Err(expression.invalid(format!("expected a number, got {}", data_type)))
+            }
+            data_type => Ok(data_type),
+        }
+    }
+
+    fn expect_textual(&self, expression: &Expression, schema: &Schema) -> Result<(), ToSQLError> {
+        match self.check_expression(expression, schema)? {
+            Some(data_type) if !is_textual(&data_type) => {
+                Err(expression.invalid(format!("expected a string, got {}", data_type)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn expect_comparable(
+        &self,
+        expression: &Expression,
+        left: &Option<DataType>,
+        right: &Option<DataType>,
+    ) -> Result<(), ToSQLError> {
+        if is_comparable(left, right) {
+            return Ok(());
+        }
+
+        Err(expression.invalid(format!(
+            "cannot compare {} with {}",
+            left.as_ref().map_or("null".to_string(), |t| t.to_string()),
+            right.as_ref().map_or("null".to_string(), |t| t.to_string()),
+        )))
+    }
+}
@@ -0,0 +1,238 @@
+use rayql::schema::{
+    error::{LoadError, ParseError},
+    parser::parse_declarations,
+    utils::{
+        resolve_model_inheritance, resolve_model_mixins, resolve_model_types, resolve_soft_delete,
+    },
+    Field, Schema,
+};
+
+use std::path::{Path, PathBuf};
+
+pub fn load(path: &Path) -> Result<Schema, LoadError> {
+    let code = read_file(path)?;
+
+    parse_file(path, &code)
+}
+
+pub(crate) fn parse_file(path: &Path, code: &str) -> Result<Schema, LoadError> {
+    let mut loader = Loader {
+        schema: Schema::default(),
+        sources: vec![],
+        loaded: vec![],
+        stack: vec![],
+    };
+
+    loader.load_source(path, code)?;
+    loader.resolve_declarations()?;
+    resolve_soft_delete(&mut loader.schema.models);
+    resolve_model_types(&mut loader.schema.models);
+
+    Ok(loader.schema)
+}
+
+struct Loader {
+    schema: Schema,
+    sources: Vec<(String, String)>,
+    loaded: Vec<PathBuf>,
+    stack: Vec<PathBuf>,
+}
+
+impl Loader {
+    fn load_source(&mut self, path: &Path, code: &str) -> Result<(), LoadError> {
+        let path = path.canonicalize().map_err(|source| LoadError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let file = path.display().to_string();
+
+        let schema = parse_declarations(code).map_err(|source| LoadError::ParseError {
+            path: file.clone(),
+            code: code.to_string(),
+            source: Box::new(source),
+        })?;
+
+        self.stack.push(path.clone());
+
+        for import in &schema.imports {
+            let import_path = match path.parent() {
+                Some(parent) => parent.join(&import.path),
+                None => PathBuf::from(&import.path),
+            };
+            let canonical_import_path =
+                import_path.canonicalize().map_err(|source| LoadError::Io {
+                    path: import_path.display().to_string(),
+                    source,
+                })?;
+
+            if self.stack.contains(&canonical_import_path) {
+                return Err(LoadError::ImportCycle {
+                    path: file,
+                    import: import.path.clone(),
+                    line_number: import.line_number,
+                    column: import.column,
+                });
+            }
+
+            if self.loaded.contains(&canonical_import_path) {
+                continue;
+            }
+
+            let import_code = read_file(&canonical_import_path)?;
+            self.load_source(&canonical_import_path, &import_code)?;
+        }
+
+        self.stack.pop();
+        self.loaded.push(path);
+        self.merge(schema, file, code)
+    }
+
+    // Mixins and parent models can be declared in any loaded file, so they are
+    // only resolved once everything is loaded.
+    fn resolve_declarations(&mut self) -> Result<(), LoadError> {
+        for model in self.schema.models.iter_mut() {
+            resolve_model_mixins(model, &self.schema.mixins)
+                .map_err(|source| source_error(&self.sources, &model.file, source))?;
+        }
+
+        let declarations = self.schema.models.clone();
+        for model in self.schema.models.iter_mut() {
+            resolve_model_inheritance(model, &declarations)
+                .map_err(|source| source_error(&self.sources, &model.file, source))?;
+        }
+
+        Ok(())
+    }
+
+    fn merge(&mut self, schema: Schema, file: String, code: &str) -> Result<(), LoadError> {
+        for mut e in schema.enums {
+            self.check_identifier(&e.name, e.line_number, e.column, &file, code)?;
+            e.file = Some(file.clone());
+            self.schema.enums.push(e);
+        }
+
+        for mut model in schema.models {
+            self.check_identifier(&model.name, model.line_number, model.column, &file, code)?;
+            model.file = Some(file.clone());
+            set_fields_file(&mut model.fields, &file);
+            for constraint in model.constraints.iter_mut() {
+                constraint.file = Some(file.clone());
+            }
+            self.schema.models.push(model);
+        }
+
+        for mut index in schema.indexes {
+            if let Some(name) = &index.name {
+                self.check_identifier(name, index.line_number, index.column, &file, code)?;
+            }
+            index.file = Some(file.clone());
+            self.schema.indexes.push(index);
+        }
+
+        for mut mixin in schema.mixins {
+            self.check_identifier(&mixin.name, mixin.line_number, mixin.column, &file, code)?;
+            mixin.file = Some(file.clone());
+            set_fields_file(&mut mixin.fields, &file);
+            self.schema.mixins.push(mixin);
+        }
+
+        for mut struct_declaration in schema.structs {
+            self.check_identifier(
+                &struct_declaration.name,
+                struct_declaration.line_number,
+                struct_declaration.column,
+                &file,
+                code,
+            )?;
+            struct_declaration.file = Some(file.clone());
+            set_fields_file(&mut struct_declaration.fields, &file);
+            self.schema.structs.push(struct_declaration);
+        }
+
+        for mut view in schema.views {
+            self.check_identifier(&view.name, view.line_number, view.column, &file, code)?;
+            view.file = Some(file.clone());
+            self.schema.views.push(view);
+        }
+
+        for mut search in schema.searches {
+            search.file = Some(file.clone());
+            self.schema.searches.push(search);
+        }
+
+        for mut seed in schema.seeds {
+            seed.file = Some(file.clone());
+            self.schema.seeds.push(seed);
+        }
+
+        self.sources.push((file, code.to_string()));
+
+        Ok(())
+    }
+
+    fn check_identifier(
+        &self,
+        identifier: &str,
+        line_number: usize,
+        column: usize,
+        file: &str,
+        code: &str,
+    ) -> Result<(), LoadError> {
+        let in_use = self.schema.get_model(identifier).is_some()
+            || self.schema.get_enum(identifier).is_some()
+            || self.schema.get_view(identifier).is_some()
+            || self.schema.get_mixin(identifier).is_some()
+            || self.schema.get_struct(identifier).is_some()
+            || self
+                .schema
+                .indexes
+                .iter()
+                .any(|index| index.name.as_deref().eq(&Some(identifier)));
+
+        if in_use {
+            return Err(LoadError::ParseError {
+                path: file.to_string(),
+                code: code.to_string(),
+                source: Box::new(ParseError::IdentifierAlreadyInUse {
+                    identifier: identifier.to_string(),
+                    line_number,
+                    column,
+                }),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn set_fields_file(fields: &mut [Field], file: &str) {
+    for field in fields.iter_mut() {
+        field.file = Some(file.to_string());
+    }
+}
+
+fn source_error(
+    sources: &[(String, String)],
+    file: &Option<String>,
+    source: ParseError,
+) -> LoadError {
+    let path = file.clone().unwrap_or_default();
+    let code = sources
+        .iter()
+        .find(|(source_path, _)| source_path.eq(&path))
+        .map(|(_, code)| code.clone())
+        .unwrap_or_default();
+
+    LoadError::ParseError {
+        path,
+        code,
+        source: Box::new(source),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, LoadError> {
+    std::fs::read_to_string(path).map_err(|source| LoadError::Io {
+        path: path.display().to_string(),
+        source,
+    })
+}
@@ -1,10 +1,16 @@
+mod expression;
+mod loader;
 mod parser;
+mod relation;
 mod tokenizer;
 mod utils;
 
 pub mod error;
 
+pub use expression::{BinaryOperator, Expression, ExpressionKind, UnaryOperator};
+pub use loader::load;
 pub use parser::parse;
+pub use relation::{ForeignKey, Relation, RelationKind};
 
 #[derive(Debug, PartialEq, Clone)]
 
@@ -28,6 +34,10 @@ impl DataTypeWithSpan {
 pub struct Enum {
     pub name: String,
     pub variants: Vec<EnumVariant>,
+    // Type of the values stored in the column, `String` or `Integer`.
+    pub data_type: rayql::types::DataType,
+    pub doc: Option<String>,
+    pub file: Option<String>,
     pub line_number: usize,
     pub column: usize,
 }
@@ -42,6 +52,9 @@ impl Enum {
         Enum {
             name,
             variants,
+            data_type: rayql::types::DataType::String,
+            doc: None,
+            file: None,
             line_number,
             column,
         }
@@ -57,6 +70,8 @@ impl Enum {
 #[derive(Debug, PartialEq, Clone)]
 pub struct EnumVariant {
     pub name: String,
+    pub value: rayql::value::Value,
+    pub doc: Option<String>,
     pub line_number: usize,
     pub column: usize,
 }
@@ -64,7 +79,9 @@ pub struct EnumVariant {
 impl EnumVariant {
     pub fn new(name: String, line_number: usize, column: usize) -> Self {
         EnumVariant {
+            value: rayql::value::Value::StringLiteral(name.clone()),
             name,
+            doc: None,
             line_number,
             column,
         }
@@ -74,11 +91,28 @@ impl EnumVariant {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Property {
     FunctionCall(FunctionCall),
+    Check(Expression),
+    Generated {
+        expression: Expression,
+        stored: bool,
+    },
     PrimaryKey,
     AutoIncrement,
     Unique,
 }
 
+// Validation rules declared with functions such as `pattern(...)` or
+// `email()`, kept structured so clients can apply the same rules.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Validation {
+    Pattern(String),
+    Like(String),
+    Length { min: Option<i64>, max: Option<i64> },
+    Between(rayql::Value, rayql::Value),
+    Email,
+    Url,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Reference {
     pub entity: String,
@@ -111,13 +145,14 @@ impl FunctionCall {
     pub fn new(
         name: String,
         arguments: Vec<Argument>,
+        named_arguments: Vec<NamedArgument>,
         context: FunctionCallContext,
         line_number: usize,
         column: usize,
     ) -> Self {
         FunctionCall {
             name,
-            arguments: Arguments::from_vec(arguments, line_number, column),
+            arguments: Arguments::new(arguments, named_arguments, line_number, column),
             context,
             line_number,
             column,
@@ -128,12 +163,15 @@ impl FunctionCall {
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionCallContext {
     pub property_name: String,
+    // The column of the property, differs from its name when it is mapped.
+    pub column_name: String,
     pub property_data_type: DataTypeWithSpan,
 }
 
 impl FunctionCallContext {
     pub fn new(property_name: String, property_data_type: DataTypeWithSpan) -> Self {
         FunctionCallContext {
+            column_name: property_name.clone(),
             property_name,
             property_data_type,
         }
@@ -143,22 +181,52 @@ impl FunctionCallContext {
 #[derive(Debug, PartialEq, Clone)]
 pub struct Arguments {
     pub list: Vec<Argument>,
+    pub named: Vec<NamedArgument>,
     pub line_number: usize,
     pub column: usize,
 }
 
 impl Arguments {
-    pub fn from_vec(arguments: Vec<Argument>, line_number: usize, column: usize) -> Self {
+    pub fn new(
+        arguments: Vec<Argument>,
+        named_arguments: Vec<NamedArgument>,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
         Arguments {
             list: arguments,
+            named: named_arguments,
             line_number,
             column,
         }
     }
 
+    pub fn from_vec(arguments: Vec<Argument>, line_number: usize, column: usize) -> Self {
+        Arguments::new(arguments, vec![], line_number, column)
+    }
+
     pub fn get_first(&self) -> Option<&Argument> {
         self.list.first()
     }
+
+    pub fn get_named(&self, name: &str) -> Option<&Argument> {
+        self.named
+            .iter()
+            .find(|named_argument| named_argument.name.eq(name))
+            .map(|named_argument| &named_argument.argument)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedArgument {
+    pub name: String,
+    pub argument: Argument,
+}
+
+impl NamedArgument {
+    pub fn new(name: String, argument: Argument) -> Self {
+        NamedArgument { name, argument }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -191,6 +259,12 @@ pub struct Field {
     pub name: String,
     pub data_type: DataTypeWithSpan,
     pub properties: Vec<Property>,
+    // The name of the column, set with `map("name")`.
+    pub map: Option<String>,
+    pub doc: Option<String>,
+    // Fields of mixins and parent models can be declared in another file than
+    // the model using them.
+    pub file: Option<String>,
     pub line_number: usize,
     pub column: usize,
 }
@@ -207,6 +281,202 @@ impl Field {
             name,
             data_type,
             properties,
+            map: None,
+            doc: None,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+
+    pub fn column_name(&self) -> &str {
+        self.map.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstraintKind {
+    PrimaryKey,
+    Unique,
+    Check(Expression),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModelConstraint {
+    pub kind: ConstraintKind,
+    pub fields: Vec<FieldName>,
+    // Constraints inherited from a parent model can be declared in another
+    // file than the model.
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl ModelConstraint {
+    pub fn new(
+        kind: ConstraintKind,
+        fields: Vec<FieldName>,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
+        ModelConstraint {
+            kind,
+            fields,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+}
+
+impl Field {
+    pub fn get_generated_expression(&self) -> Option<&Expression> {
+        self.properties.iter().find_map(|property| match property {
+            Property::Generated { expression, .. } => Some(expression),
+            _ => None,
+        })
+    }
+
+    // Generated columns are computed by the database and cannot be written to.
+    pub fn is_read_only(&self) -> bool {
+        self.get_generated_expression().is_some()
+    }
+
+    pub fn has_function_call(&self, function_name: &str) -> bool {
+        self.get_function_call(function_name).is_some()
+    }
+
+    pub fn get_function_call(&self, function_name: &str) -> Option<&FunctionCall> {
+        self.properties.iter().find_map(|property| match property {
+            Property::FunctionCall(func) if func.name.eq(function_name) => Some(func),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Mixin {
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub doc: Option<String>,
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Mixin {
+    pub fn new(name: String, fields: Vec<Field>, line_number: usize, column: usize) -> Self {
+        Mixin {
+            name,
+            fields,
+            doc: None,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+}
+
+// A struct-like declaration describing the shape of `json<name>` fields, it
+// has no table of its own.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub doc: Option<String>,
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Struct {
+    pub fn new(name: String, fields: Vec<Field>, line_number: usize, column: usize) -> Self {
+        Struct {
+            name,
+            fields,
+            doc: None,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+
+    pub fn get_field(&self, field_name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name.eq(field_name))
+    }
+}
+
+// A `use mixin` inside a model, `position` is the index in `Model.fields` at
+// which the mixin's fields are spliced.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MixinUse {
+    pub name: String,
+    pub position: usize,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl MixinUse {
+    pub fn new(name: String, position: usize, line_number: usize, column: usize) -> Self {
+        MixinUse {
+            name,
+            position,
+            line_number,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModelOptionKind {
+    Strict,
+    WithoutRowid,
+    SoftDelete,
+}
+
+// The column marking soft deleted rows, added to `soft_delete` models which
+// do not declare it.
+pub const SOFT_DELETE_FIELD: &str = "deleted_at";
+
+impl std::fmt::Display for ModelOptionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelOptionKind::Strict => write!(f, "strict"),
+            ModelOptionKind::WithoutRowid => write!(f, "without_rowid"),
+            ModelOptionKind::SoftDelete => write!(f, "soft_delete"),
+        }
+    }
+}
+
+// An option written after the model name, e.g. `model user strict { ... }`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModelOption {
+    pub kind: ModelOptionKind,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl ModelOption {
+    pub fn new(kind: ModelOptionKind, line_number: usize, column: usize) -> Self {
+        ModelOption {
+            kind,
+            line_number,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Extends {
+    pub model: String,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Extends {
+    pub fn new(model: String, line_number: usize, column: usize) -> Self {
+        Extends {
+            model,
             line_number,
             column,
         }
@@ -217,15 +487,38 @@ impl Field {
 pub struct Model {
     pub name: String,
     pub fields: Vec<Field>,
+    pub constraints: Vec<ModelConstraint>,
+    pub mixins: Vec<MixinUse>,
+    pub options: Vec<ModelOption>,
+    // The name of the table, set with `map("name")`.
+    pub map: Option<String>,
+    pub is_abstract: bool,
+    pub extends: Option<Extends>,
+    pub doc: Option<String>,
+    pub file: Option<String>,
     pub line_number: usize,
     pub column: usize,
 }
 
 impl Model {
-    pub fn new(name: String, fields: Vec<Field>, line_number: usize, column: usize) -> Self {
+    pub fn new(
+        name: String,
+        fields: Vec<Field>,
+        constraints: Vec<ModelConstraint>,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
         Model {
             name,
             fields,
+            constraints,
+            mixins: vec![],
+            options: vec![],
+            map: None,
+            is_abstract: false,
+            extends: None,
+            doc: None,
+            file: None,
             line_number,
             column,
         }
@@ -234,23 +527,296 @@ impl Model {
     pub fn get_field(&self, field_name: &str) -> Option<&Field> {
         self.fields.iter().find(|field| field.name.eq(field_name))
     }
+
+    pub fn table_name(&self) -> &str {
+        self.map.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn get_option(&self, kind: &ModelOptionKind) -> Option<&ModelOption> {
+        self.options.iter().find(|option| option.kind.eq(kind))
+    }
+
+    // Rows of soft delete models are deleted by setting `deleted_at`, queries
+    // should only see the rows where it is null.
+    pub fn is_soft_delete(&self) -> bool {
+        self.get_option(&ModelOptionKind::SoftDelete).is_some()
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct FieldName {
+    pub name: String,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl FieldName {
+    pub fn new(name: String, line_number: usize, column: usize) -> Self {
+        FieldName {
+            name,
+            line_number,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Index {
+    pub name: Option<String>,
+    pub model: String,
+    pub columns: Vec<FieldName>,
+    pub unique: bool,
+    // Only rows matching the condition are indexed, set with `where ...`.
+    pub condition: Option<Expression>,
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Index {
+    pub fn new(
+        name: Option<String>,
+        model: String,
+        columns: Vec<FieldName>,
+        unique: bool,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
+        Index {
+            name,
+            model,
+            columns,
+            unique,
+            condition: None,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let columns: Vec<&str> = self.columns.iter().map(|c| c.name.as_str()).collect();
+                format!("{}_{}_idx", self.model, columns.join("_"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Import {
+    pub path: String,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Import {
+    pub fn new(path: String, line_number: usize, column: usize) -> Self {
+        Import {
+            path,
+            line_number,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ViewColumn {
+    pub alias: Option<String>,
+    pub reference: Reference,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl ViewColumn {
+    pub fn new(
+        alias: Option<String>,
+        reference: Reference,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
+        ViewColumn {
+            alias,
+            reference,
+            line_number,
+            column,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.reference.property)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JoinKind {
+    Inner,
+    Left,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ViewJoin {
+    pub kind: JoinKind,
+    pub left: Reference,
+    pub right: Reference,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl ViewJoin {
+    pub fn new(
+        kind: JoinKind,
+        left: Reference,
+        right: Reference,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
+        ViewJoin {
+            kind,
+            left,
+            right,
+            line_number,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct View {
+    pub name: String,
+    pub columns: Vec<ViewColumn>,
+    pub joins: Vec<ViewJoin>,
+    pub doc: Option<String>,
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl View {
+    pub fn new(
+        name: String,
+        columns: Vec<ViewColumn>,
+        joins: Vec<ViewJoin>,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
+        View {
+            name,
+            columns,
+            joins,
+            doc: None,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+
+    pub fn get_column(&self, column_name: &str) -> Option<&ViewColumn> {
+        self.columns
+            .iter()
+            .find(|column| column.get_name().eq(column_name))
+    }
+}
+
+// A full-text search table kept in sync with a model, declared with
+// `search model(field, ...)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Search {
+    pub model: String,
+    pub fields: Vec<FieldName>,
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Search {
+    pub fn new(model: String, fields: Vec<FieldName>, line_number: usize, column: usize) -> Self {
+        Search {
+            model,
+            fields,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+}
+
+// Rows inserted once the tables are created, declared with `seed model { ... }`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Seed {
+    pub model: String,
+    // The unique field used to update existing rows, set with `upsert(field)`.
+    pub upsert: Option<FieldName>,
+    pub rows: Vec<SeedRow>,
+    pub file: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Seed {
+    pub fn new(
+        model: String,
+        upsert: Option<FieldName>,
+        rows: Vec<SeedRow>,
+        line_number: usize,
+        column: usize,
+    ) -> Self {
+        Seed {
+            model,
+            upsert,
+            rows,
+            file: None,
+            line_number,
+            column,
+        }
+    }
+}
+
+// A row of a seed, written as `(field: value, ...)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SeedRow {
+    pub values: Arguments,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl SeedRow {
+    pub fn new(values: Arguments, line_number: usize, column: usize) -> Self {
+        SeedRow {
+            values,
+            line_number,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
 pub struct Schema {
+    pub imports: Vec<Import>,
     pub enums: Vec<Enum>,
     pub models: Vec<Model>,
+    pub indexes: Vec<Index>,
+    pub views: Vec<View>,
+    pub mixins: Vec<Mixin>,
+    pub structs: Vec<Struct>,
+    pub searches: Vec<Search>,
+    pub seeds: Vec<Seed>,
 }
 
 impl Schema {
-    pub fn new(enums: Vec<Enum>, models: Vec<Model>) -> Self {
-        Schema { enums, models }
-    }
-
     pub fn parse(input: &str) -> Result<rayql::Schema, error::ParseError> {
         rayql::schema::parse(input)
     }
 
+    pub fn parse_file(
+        path: &std::path::Path,
+        input: &str,
+    ) -> Result<rayql::Schema, error::LoadError> {
+        rayql::schema::loader::parse_file(path, input)
+    }
+
     pub fn get_model(&self, model_name: &str) -> Option<&Model> {
         self.models.iter().find(|model| model.name.eq(model_name))
     }
@@ -258,4 +824,16 @@ impl Schema {
     pub fn get_enum(&self, enum_name: &str) -> Option<&Enum> {
         self.enums.iter().find(|e| e.name.eq(enum_name))
     }
+
+    pub fn get_mixin(&self, mixin_name: &str) -> Option<&Mixin> {
+        self.mixins.iter().find(|mixin| mixin.name.eq(mixin_name))
+    }
+
+    pub fn get_struct(&self, struct_name: &str) -> Option<&Struct> {
+        self.structs.iter().find(|s| s.name.eq(struct_name))
+    }
+
+    pub fn get_view(&self, view_name: &str) -> Option<&View> {
+        self.views.iter().find(|view| view.name.eq(view_name))
+    }
 }
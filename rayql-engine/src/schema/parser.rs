@@ -1,8 +1,11 @@
 use rayql::schema::{
     error::ParseError,
     tokenizer::{tokenize, Keyword, Token},
-    utils::{get_data_type_with_span, get_model_or_enum_name},
-    Argument, Schema,
+    utils::{
+        get_data_type_with_span, get_declaration_name, get_model_or_enum_name, resolve_inheritance,
+        resolve_mixins, resolve_model_types, resolve_soft_delete,
+    },
+    Argument, BinaryOperator, Expression, ExpressionKind, FieldName, Schema, UnaryOperator,
 };
 
 struct TokenConsumer<'a> {
@@ -30,25 +33,134 @@ impl<'a> TokenConsumer<'a> {
 }
 
 pub fn parse(input: &str) -> Result<Schema, ParseError> {
+    let mut schema = parse_declarations(input)?;
+
+    resolve_mixins(&mut schema.models, &schema.mixins)?;
+    resolve_inheritance(&mut schema.models)?;
+    resolve_soft_delete(&mut schema.models);
+    resolve_model_types(&mut schema.models);
+
+    Ok(schema)
+}
+
+// Parses the declarations of a single source, mixins, inheritance and model
+// types are resolved once every declaration of the schema is known.
+pub(crate) fn parse_declarations(input: &str) -> Result<Schema, ParseError> {
     let tokens = tokenize(input)?;
+    let mut imports = Vec::new();
     let mut models = Vec::new();
     let mut enums = Vec::new();
+    let mut indexes = Vec::new();
+    let mut views = Vec::new();
+    let mut mixins = Vec::new();
+    let mut structs = Vec::new();
+    let mut searches = Vec::new();
+    let mut seeds = Vec::new();
     let mut identifiers = std::collections::HashSet::new();
     let mut token_consumer = TokenConsumer::new(&tokens);
+    let mut doc = None;
 
     while let Some((token, line_number, column)) = token_consumer.next() {
+        let pending_doc = doc.take();
+
         match token {
+            Token::DocComment(comment) => doc = Some(comment.clone()),
             Token::Keyword(Keyword::Enum) => {
                 let enum_name =
-                    get_model_or_enum_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
-                let enum_declaration = parse_enum(enum_name, &mut token_consumer)?;
+                    get_declaration_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
+                let data_type = parse_enum_data_type(&mut token_consumer)?;
+                let mut enum_declaration = parse_enum(enum_name, data_type, &mut token_consumer)?;
+                enum_declaration.doc = pending_doc;
                 enums.push(enum_declaration);
             }
-            Token::Keyword(Keyword::Model) => {
+            Token::Keyword(keyword @ (Keyword::Model | Keyword::Abstract)) => {
+                if keyword.eq(&Keyword::Abstract) {
+                    expect_token(Token::Keyword(Keyword::Model), &mut token_consumer)?;
+                }
+
                 let model_name =
-                    get_model_or_enum_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
-                let model_declaration = parse_model(model_name, &mut token_consumer)?;
+                    get_declaration_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
+                let extends = parse_extends(&mut token_consumer)?;
+                let options = parse_model_options(&mut token_consumer)?;
+                let (mut model_declaration, model_indexes) =
+                    parse_model(model_name, &mut token_consumer, &mut identifiers)?;
+                model_declaration.is_abstract = keyword.eq(&Keyword::Abstract);
+                model_declaration.extends = extends;
+                model_declaration.options = options;
+                model_declaration.doc = pending_doc;
                 models.push(model_declaration);
+                indexes.extend(model_indexes);
+            }
+            Token::Keyword(Keyword::Import) => match token_consumer.next() {
+                Some((Token::StringLiteral(path), _, _)) => imports.push(
+                    rayql::schema::Import::new(path.clone(), line_number, column),
+                ),
+                Some((token, line_number, column)) => {
+                    return Err(ParseError::UnexpectedToken {
+                        token: token.clone(),
+                        line_number,
+                        column,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEndOfTokens),
+            },
+            Token::Keyword(Keyword::Index) => {
+                let index = parse_index(
+                    None,
+                    &mut token_consumer,
+                    &mut identifiers,
+                    line_number,
+                    column,
+                )?;
+                indexes.push(index);
+            }
+            Token::Identifier(identifier) if identifier.eq("mixin") => {
+                let mixin_name =
+                    get_model_or_enum_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
+                let fields = parse_field_block(&mixin_name, &mut token_consumer)?;
+                let mut mixin = rayql::schema::Mixin::new(mixin_name, fields, line_number, column);
+                mixin.doc = pending_doc;
+                mixins.push(mixin);
+            }
+            Token::Identifier(identifier) if identifier.eq("struct") => {
+                let struct_name =
+                    get_model_or_enum_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
+                let fields = parse_field_block(&struct_name, &mut token_consumer)?;
+                let mut struct_declaration =
+                    rayql::schema::Struct::new(struct_name, fields, line_number, column);
+                struct_declaration.doc = pending_doc;
+                structs.push(struct_declaration);
+            }
+            Token::Identifier(identifier) if identifier.eq("view") => {
+                let view_name =
+                    get_model_or_enum_name(&mut token_consumer.tokens_iter, &mut identifiers)?;
+                let mut view = parse_view(view_name, &mut token_consumer, line_number, column)?;
+                view.doc = pending_doc;
+                views.push(view);
+            }
+            Token::Identifier(identifier) if identifier.eq("search") => {
+                let model_name = match token_consumer.next() {
+                    Some((Token::Identifier(model_name), _, _)) => model_name.clone(),
+                    Some((token, line_number, column)) => {
+                        return Err(ParseError::UnexpectedToken {
+                            token: token.clone(),
+                            line_number,
+                            column,
+                        })
+                    }
+                    None => return Err(ParseError::UnexpectedEndOfTokens),
+                };
+                expect_token(Token::ParenOpen, &mut token_consumer)?;
+                let fields = parse_field_name_list(&mut token_consumer)?;
+                searches.push(rayql::schema::Search::new(
+                    model_name,
+                    fields,
+                    line_number,
+                    column,
+                ));
+            }
+            Token::Identifier(identifier) if identifier.eq("seed") => {
+                seeds.push(parse_seed(&mut token_consumer, line_number, column)?);
             }
             _ => {
                 return Err(ParseError::UnexpectedToken {
@@ -60,25 +172,72 @@ pub fn parse(input: &str) -> Result<Schema, ParseError> {
         }
     }
 
-    Ok(Schema::new(enums, models))
+    Ok(Schema {
+        imports,
+        enums,
+        models,
+        indexes,
+        views,
+        mixins,
+        structs,
+        searches,
+        seeds,
+    })
+}
+
+// Parses the optional `: int` or `: str` of an enum declaration along with
+// the opening brace.
+fn parse_enum_data_type(
+    token_consumer: &mut TokenConsumer,
+) -> Result<rayql::types::DataType, ParseError> {
+    let data_type = match token_consumer.next() {
+        Some((Token::BraceOpen, _, _)) => return Ok(rayql::types::DataType::String),
+        Some((Token::Colon, _, _)) => match token_consumer.next() {
+            Some((Token::Keyword(Keyword::Integer), _, _)) => rayql::types::DataType::Integer,
+            Some((Token::Keyword(Keyword::String), _, _)) => rayql::types::DataType::String,
+            Some((token, line_number, column)) => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        },
+        Some((token, line_number, column)) => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    expect_token(Token::BraceOpen, token_consumer)?;
+
+    Ok(data_type)
 }
 
 fn parse_enum(
     enum_name: String,
+    data_type: rayql::types::DataType,
     token_consumer: &mut TokenConsumer,
 ) -> Result<rayql::schema::Enum, ParseError> {
-    let mut variants = vec![];
+    let mut variants: Vec<rayql::schema::EnumVariant> = vec![];
     let mut existing_variants = std::collections::HashSet::new();
+    let mut doc = None;
 
     while let Some((token, line_number, column)) = token_consumer.next() {
+        let pending_doc = doc.take();
+
         match token {
+            Token::DocComment(comment) => doc = Some(comment.clone()),
             Token::BraceClose => {
-                return Ok(rayql::schema::Enum::new(
-                    enum_name,
-                    variants,
-                    line_number,
-                    column,
-                ))
+                let mut enum_declaration =
+                    rayql::schema::Enum::new(enum_name, variants, line_number, column);
+                enum_declaration.data_type = data_type;
+                return Ok(enum_declaration);
             }
             Token::Identifier(variant) => {
                 if !existing_variants.insert(variant) {
@@ -90,12 +249,259 @@ fn parse_enum(
                     });
                 }
 
-                variants.push(rayql::schema::EnumVariant::new(
-                    variant.clone(),
+                let mut enum_variant =
+                    rayql::schema::EnumVariant::new(variant.clone(), line_number, column);
+                enum_variant.doc = pending_doc;
+
+                // int enums are numbered from 0, following the previous value
+                if data_type.eq(&rayql::types::DataType::Integer) {
+                    enum_variant.value = match variants.last().map(|last| &last.value) {
+                        Some(rayql::value::Value::Integer(last)) => {
+                            rayql::value::Value::Integer(last + 1)
+                        }
+                        _ => rayql::value::Value::Integer(0),
+                    };
+                }
+
+                if let Some((Token::Operator(operator), _, _)) = token_consumer.peek() {
+                    if operator.eq("=") {
+                        token_consumer.next();
+                        enum_variant.value =
+                            parse_enum_value(&enum_name, variant, &data_type, token_consumer)?;
+                    }
+                }
+
+                if let Some(existing) = variants
+                    .iter()
+                    .find(|existing| existing.value.eq(&enum_variant.value))
+                {
+                    return Err(ParseError::InvalidEnumVariantValue {
+                        variant: variant.clone(),
+                        r#enum: enum_name,
+                        reason: format!(
+                            "value {} is already used by '{}'",
+                            enum_variant.value, existing.name
+                        ),
+                        line_number,
+                        column,
+                    });
+                }
+
+                variants.push(enum_variant);
+
+                if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+                    token_consumer.next();
+                }
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+        }
+    }
+
+    Err(ParseError::UnexpectedEndOfTokens)
+}
+
+fn parse_enum_value(
+    enum_name: &str,
+    variant: &str,
+    data_type: &rayql::types::DataType,
+    token_consumer: &mut TokenConsumer,
+) -> Result<rayql::value::Value, ParseError> {
+    let (value, line_number, column) = match token_consumer.next() {
+        Some((Token::Integer(i), line_number, column)) => {
+            (rayql::value::Value::Integer(*i), line_number, column)
+        }
+        Some((Token::StringLiteral(s), line_number, column)) => (
+            rayql::value::Value::StringLiteral(s.clone()),
+            line_number,
+            column,
+        ),
+        Some((token, line_number, column)) => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    if value.get_type().ne(data_type) {
+        return Err(ParseError::InvalidEnumVariantValue {
+            variant: variant.to_string(),
+            r#enum: enum_name.to_string(),
+            reason: format!("expected a value of type {}, got {}", data_type, value),
+            line_number,
+            column,
+        });
+    }
+
+    Ok(value)
+}
+
+// Parses the optional `extends parent` of a model declaration.
+fn parse_extends(
+    token_consumer: &mut TokenConsumer,
+) -> Result<Option<rayql::schema::Extends>, ParseError> {
+    let extends = if peek_word(token_consumer, "extends") {
+        token_consumer.next();
+        match token_consumer.next() {
+            Some((Token::Identifier(parent), line_number, column)) => Some(
+                rayql::schema::Extends::new(parent.clone(), line_number, column),
+            ),
+            Some((token, line_number, column)) => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
                     line_number,
                     column,
+                })
+            }
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        }
+    } else {
+        None
+    };
+
+    Ok(extends)
+}
+
+// Parses the options written before the opening brace of a model, such as
+// `strict`, `without_rowid` or `soft_delete`.
+fn parse_model_options(
+    token_consumer: &mut TokenConsumer,
+) -> Result<Vec<rayql::schema::ModelOption>, ParseError> {
+    let mut options: Vec<rayql::schema::ModelOption> = vec![];
+
+    loop {
+        let (token, line_number, column) = match token_consumer.next() {
+            Some((Token::BraceOpen, _, _)) => return Ok(options),
+            Some(next) => next,
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        };
+
+        let kind = match token {
+            Token::Identifier(option) if option.eq("strict") => {
+                rayql::schema::ModelOptionKind::Strict
+            }
+            Token::Identifier(option) if option.eq("without_rowid") => {
+                rayql::schema::ModelOptionKind::WithoutRowid
+            }
+            Token::Identifier(option) if option.eq("soft_delete") => {
+                rayql::schema::ModelOptionKind::SoftDelete
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+        };
+
+        if options.iter().any(|option| option.kind.eq(&kind)) {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            });
+        }
+
+        options.push(rayql::schema::ModelOption::new(kind, line_number, column));
+    }
+}
+
+// Parses `seed model upsert(field) { (field: value, ...), ... }`, the rows are
+// checked against the model when the SQL is generated.
+fn parse_seed(
+    token_consumer: &mut TokenConsumer,
+    seed_line: usize,
+    seed_column: usize,
+) -> Result<rayql::schema::Seed, ParseError> {
+    let model_name = match token_consumer.next() {
+        Some((Token::Identifier(model_name), _, _)) => model_name.clone(),
+        Some((token, line_number, column)) => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    let upsert = match token_consumer.next() {
+        Some((Token::BraceOpen, _, _)) => None,
+        Some((Token::Identifier(identifier), _, _)) if identifier.eq("upsert") => {
+            expect_token(Token::ParenOpen, token_consumer)?;
+            let field_name = match token_consumer.next() {
+                Some((Token::Identifier(name), line_number, column)) => {
+                    FieldName::new(name.clone(), line_number, column)
+                }
+                Some((token, line_number, column)) => {
+                    return Err(ParseError::UnexpectedToken {
+                        token: token.clone(),
+                        line_number,
+                        column,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEndOfTokens),
+            };
+            expect_token(Token::ParenClose, token_consumer)?;
+            expect_token(Token::BraceOpen, token_consumer)?;
+            Some(field_name)
+        }
+        Some((token, line_number, column)) => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    let mut rows = vec![];
+
+    while let Some((token, line_number, column)) = token_consumer.next() {
+        match token {
+            // seed rows carry no documentation, doc comments are ignored
+            Token::DocComment(_) => {}
+            Token::BraceClose => {
+                return Ok(rayql::schema::Seed::new(
+                    model_name,
+                    upsert,
+                    rows,
+                    seed_line,
+                    seed_column,
                 ))
             }
+            Token::ParenOpen => {
+                // rows are read like the arguments of a function call
+                let row = parse_function_call(
+                    model_name.clone(),
+                    rayql::schema::FunctionCallContext::new(
+                        model_name.clone(),
+                        rayql::schema::DataTypeWithSpan::new(
+                            rayql::types::DataType::Model(model_name.clone()),
+                            line_number,
+                            column,
+                        ),
+                    ),
+                    token_consumer,
+                    line_number,
+                    column,
+                )?;
+                rows.push(rayql::schema::SeedRow::new(
+                    row.arguments,
+                    line_number,
+                    column,
+                ));
+            }
             _ => {
                 return Err(ParseError::UnexpectedToken {
                     token: token.clone(),
@@ -104,6 +510,10 @@ fn parse_enum(
                 })
             }
         }
+
+        if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+            token_consumer.next();
+        }
     }
 
     Err(ParseError::UnexpectedEndOfTokens)
@@ -112,32 +522,142 @@ fn parse_enum(
 fn parse_model(
     model_name: String,
     token_consumer: &mut TokenConsumer,
-) -> Result<rayql::schema::Model, ParseError> {
+    identifiers: &mut std::collections::HashSet<String>,
+) -> Result<(rayql::schema::Model, Vec<rayql::schema::Index>), ParseError> {
     let mut fields = vec![];
-    let mut field_names = std::collections::HashSet::new();
+    let mut constraints = vec![];
+    let mut indexes = vec![];
+    let mut mixins = vec![];
+    let mut map = None;
+    let mut doc = None;
 
     while let Some((token, line_number, column)) = token_consumer.next() {
+        let pending_doc = doc.take();
+
         match token {
+            Token::DocComment(comment) => doc = Some(comment.clone()),
             Token::BraceClose => {
-                return Ok(rayql::schema::Model::new(
-                    model_name,
+                let mut model =
+                    rayql::schema::Model::new(model_name, fields, constraints, line_number, column);
+                model.mixins = mixins;
+                model.map = map;
+                return Ok((model, indexes));
+            }
+            Token::Keyword(Keyword::Index) => {
+                let index = parse_index(
+                    Some(&model_name),
+                    token_consumer,
+                    identifiers,
+                    line_number,
+                    column,
+                )?;
+                indexes.push(index);
+
+                if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+                    token_consumer.next();
+                }
+            }
+            Token::Keyword(keyword @ (Keyword::PrimaryKey | Keyword::Unique)) => {
+                let kind = match keyword {
+                    Keyword::PrimaryKey => rayql::schema::ConstraintKind::PrimaryKey,
+                    _ => rayql::schema::ConstraintKind::Unique,
+                };
+
+                expect_token(Token::ParenOpen, token_consumer)?;
+                let fields = parse_field_name_list(token_consumer)?;
+                for (position, field) in fields.iter().enumerate() {
+                    if let Some(other) = fields[..position].iter().find(|f| f.name.eq(&field.name))
+                    {
+                        return Err(ParseError::FieldAlreadyInConstraint {
+                            field: field.name.clone(),
+                            model: model_name,
+                            line_number: field.line_number,
+                            column: field.column,
+                            other_line_number: other.line_number,
+                            other_column: other.column,
+                        });
+                    }
+                }
+                constraints.push(rayql::schema::ModelConstraint::new(
+                    kind,
                     fields,
                     line_number,
                     column,
-                ))
+                ));
+
+                if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+                    token_consumer.next();
+                }
+            }
+            Token::Identifier(identifier)
+                if identifier.eq("check")
+                    && matches!(token_consumer.peek(), Some((Token::ParenOpen, _, _))) =>
+            {
+                token_consumer.next();
+                let expression = parse_expression(token_consumer)?;
+                expect_token(Token::ParenClose, token_consumer)?;
+                constraints.push(rayql::schema::ModelConstraint::new(
+                    rayql::schema::ConstraintKind::Check(expression),
+                    vec![],
+                    line_number,
+                    column,
+                ));
+
+                if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+                    token_consumer.next();
+                }
+            }
+            Token::Identifier(identifier)
+                if identifier.eq("map")
+                    && map.is_none()
+                    && matches!(token_consumer.peek(), Some((Token::ParenOpen, _, _))) =>
+            {
+                token_consumer.next();
+                map = Some(parse_map(token_consumer)?);
+
+                if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+                    token_consumer.next();
+                }
+            }
+            Token::Identifier(identifier)
+                if identifier.eq("use")
+                    && matches!(token_consumer.peek(), Some((Token::Identifier(_), _, _))) =>
+            {
+                if let Some((Token::Identifier(mixin), mixin_line, mixin_column)) =
+                    token_consumer.next()
+                {
+                    mixins.push(rayql::schema::MixinUse::new(
+                        mixin.clone(),
+                        fields.len(),
+                        mixin_line,
+                        mixin_column,
+                    ));
+                }
+
+                if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+                    token_consumer.next();
+                }
             }
             Token::Identifier(identifier) => match token_consumer.next() {
                 Some((Token::Colon, _, _)) => {
-                    if !field_names.insert(identifier) {
+                    if let Some(existing) = fields
+                        .iter()
+                        .find(|field: &&rayql::schema::Field| field.name.eq(identifier))
+                    {
                         return Err(ParseError::FieldAlreadyExistsOnModel {
                             field: identifier.clone(),
                             model: model_name,
                             line_number,
                             column,
+                            other_line_number: existing.line_number,
+                            other_column: existing.column,
+                            other_file: None,
                         });
                     }
 
-                    let field = parse_field(identifier.clone(), token_consumer)?;
+                    let mut field =
+                        parse_field(identifier.clone(), token_consumer, line_number, column)?;
+                    field.doc = pending_doc;
                     fields.push(field);
                 }
                 Some((token, line_number, column)) => {
@@ -162,30 +682,404 @@ fn parse_model(
     Err(ParseError::UnexpectedEndOfTokens)
 }
 
+// Parses a block made only of fields, as used by `mixin` and `struct`
+// declarations, the opening brace must already be consumed.
+fn parse_field_block(
+    block_name: &str,
+    token_consumer: &mut TokenConsumer,
+) -> Result<Vec<rayql::schema::Field>, ParseError> {
+    let mut fields: Vec<rayql::schema::Field> = vec![];
+    let mut doc = None;
+
+    while let Some((token, line_number, column)) = token_consumer.next() {
+        let pending_doc = doc.take();
+
+        match token {
+            Token::DocComment(comment) => doc = Some(comment.clone()),
+            Token::BraceClose => return Ok(fields),
+            Token::Identifier(identifier) => {
+                expect_token(Token::Colon, token_consumer)?;
+
+                if let Some(existing) = fields.iter().find(|field| field.name.eq(identifier)) {
+                    return Err(ParseError::FieldAlreadyExistsOnModel {
+                        field: identifier.clone(),
+                        model: block_name.to_string(),
+                        line_number,
+                        column,
+                        other_line_number: existing.line_number,
+                        other_column: existing.column,
+                        other_file: None,
+                    });
+                }
+
+                let mut field =
+                    parse_field(identifier.clone(), token_consumer, line_number, column)?;
+                field.doc = pending_doc;
+                fields.push(field);
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+        }
+    }
+
+    Err(ParseError::UnexpectedEndOfTokens)
+}
+
+fn parse_index(
+    model_name: Option<&str>,
+    token_consumer: &mut TokenConsumer,
+    identifiers: &mut std::collections::HashSet<String>,
+    index_line: usize,
+    index_column: usize,
+) -> Result<rayql::schema::Index, ParseError> {
+    let (name, model) = match model_name {
+        // `index [name](columns)` inside a model declaration
+        Some(model_name) => match token_consumer.peek() {
+            Some((Token::Identifier(name), line_number, column)) => {
+                token_consumer.next();
+                let name = register_index_name(name, identifiers, line_number, column)?;
+                expect_token(Token::ParenOpen, token_consumer)?;
+                (Some(name), model_name.to_string())
+            }
+            Some((Token::ParenOpen, _, _)) => {
+                token_consumer.next();
+                (None, model_name.to_string())
+            }
+            Some((token, line_number, column)) => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        },
+        // `index [name] on model(columns)` at the top level
+        None => {
+            let name = match token_consumer.next() {
+                Some((Token::Identifier(on), _, _)) if on.eq("on") => None,
+                Some((Token::Identifier(name), line_number, column)) => {
+                    let name = register_index_name(name, identifiers, line_number, column)?;
+                    match token_consumer.next() {
+                        Some((Token::Identifier(on), _, _)) if on.eq("on") => Some(name),
+                        Some((token, line_number, column)) => {
+                            return Err(ParseError::UnexpectedToken {
+                                token: token.clone(),
+                                line_number,
+                                column,
+                            })
+                        }
+                        None => return Err(ParseError::UnexpectedEndOfTokens),
+                    }
+                }
+                Some((token, line_number, column)) => {
+                    return Err(ParseError::UnexpectedToken {
+                        token: token.clone(),
+                        line_number,
+                        column,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEndOfTokens),
+            };
+
+            let model = match token_consumer.next() {
+                Some((Token::Identifier(model), _, _)) => model.clone(),
+                Some((token, line_number, column)) => {
+                    return Err(ParseError::UnexpectedToken {
+                        token: token.clone(),
+                        line_number,
+                        column,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEndOfTokens),
+            };
+
+            expect_token(Token::ParenOpen, token_consumer)?;
+
+            (name, model)
+        }
+    };
+
+    let columns = parse_field_name_list(token_consumer)?;
+
+    let unique = match token_consumer.peek() {
+        Some((Token::Keyword(Keyword::Unique), _, _)) => {
+            token_consumer.next();
+            true
+        }
+        _ => false,
+    };
+
+    let mut index =
+        rayql::schema::Index::new(name, model, columns, unique, index_line, index_column);
+
+    if peek_word(token_consumer, "where") {
+        token_consumer.next();
+        index.condition = Some(parse_expression(token_consumer)?);
+    }
+
+    Ok(index)
+}
+
+// Parses the body of `view name { [alias:] model.field, join(a.x, b.y), ... }`,
+// the opening brace must already be consumed.
+fn parse_view(
+    view_name: String,
+    token_consumer: &mut TokenConsumer,
+    view_line: usize,
+    view_column: usize,
+) -> Result<rayql::schema::View, ParseError> {
+    let mut columns: Vec<rayql::schema::ViewColumn> = vec![];
+    let mut joins = vec![];
+
+    while let Some((token, line_number, column)) = token_consumer.next() {
+        match token {
+            // view columns carry no documentation, doc comments are ignored
+            Token::DocComment(_) => {}
+            Token::BraceClose => {
+                return Ok(rayql::schema::View::new(
+                    view_name,
+                    columns,
+                    joins,
+                    view_line,
+                    view_column,
+                ))
+            }
+            Token::Identifier(identifier)
+                if (identifier.eq("join") || identifier.eq("left_join"))
+                    && matches!(token_consumer.peek(), Some((Token::ParenOpen, _, _))) =>
+            {
+                token_consumer.next();
+                let kind = match identifier.as_str() {
+                    "left_join" => rayql::schema::JoinKind::Left,
+                    _ => rayql::schema::JoinKind::Inner,
+                };
+                let left = parse_reference(token_consumer)?;
+                expect_token(Token::Comma, token_consumer)?;
+                let right = parse_reference(token_consumer)?;
+                expect_token(Token::ParenClose, token_consumer)?;
+                joins.push(rayql::schema::ViewJoin::new(
+                    kind,
+                    left,
+                    right,
+                    line_number,
+                    column,
+                ));
+            }
+            Token::Identifier(alias) => {
+                expect_token(Token::Colon, token_consumer)?;
+                let reference = parse_reference(token_consumer)?;
+                push_view_column(
+                    &view_name,
+                    &mut columns,
+                    rayql::schema::ViewColumn::new(
+                        Some(alias.clone()),
+                        reference,
+                        line_number,
+                        column,
+                    ),
+                )?;
+            }
+            Token::Reference(entity, property) => {
+                let reference = reference_from_token(entity, property, line_number, column)?;
+                push_view_column(
+                    &view_name,
+                    &mut columns,
+                    rayql::schema::ViewColumn::new(None, reference, line_number, column),
+                )?;
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+        }
+
+        if let Some((Token::Comma, _, _)) = token_consumer.peek() {
+            token_consumer.next();
+        }
+    }
+
+    Err(ParseError::UnexpectedEndOfTokens)
+}
+
+fn push_view_column(
+    view_name: &str,
+    columns: &mut Vec<rayql::schema::ViewColumn>,
+    view_column: rayql::schema::ViewColumn,
+) -> Result<(), ParseError> {
+    if columns
+        .iter()
+        .any(|existing| existing.get_name().eq(view_column.get_name()))
+    {
+        return Err(ParseError::ColumnAlreadyExistsOnView {
+            name: view_column.get_name().to_string(),
+            view: view_name.to_string(),
+            line_number: view_column.line_number,
+            column: view_column.column,
+        });
+    }
+
+    columns.push(view_column);
+
+    Ok(())
+}
+
+fn parse_reference(
+    token_consumer: &mut TokenConsumer,
+) -> Result<rayql::schema::Reference, ParseError> {
+    match token_consumer.next() {
+        Some((Token::Reference(entity, property), line_number, column)) => {
+            reference_from_token(entity, property, line_number, column)
+        }
+        Some((token, line_number, column)) => Err(ParseError::UnexpectedToken {
+            token: token.clone(),
+            line_number,
+            column,
+        }),
+        None => Err(ParseError::UnexpectedEndOfTokens),
+    }
+}
+
+fn reference_from_token(
+    entity: &str,
+    property: &str,
+    line_number: usize,
+    column: usize,
+) -> Result<rayql::schema::Reference, ParseError> {
+    if property.contains('.') {
+        return Err(ParseError::InvalidReference {
+            entity: entity.to_string(),
+            property: property.to_string(),
+            line_number,
+            column,
+        });
+    }
+
+    Ok(rayql::schema::Reference::new(
+        entity.to_string(),
+        property.to_string(),
+        line_number,
+        column,
+    ))
+}
+
+fn register_index_name(
+    name: &str,
+    identifiers: &mut std::collections::HashSet<String>,
+    line_number: usize,
+    column: usize,
+) -> Result<String, ParseError> {
+    if !identifiers.insert(name.to_string()) {
+        return Err(ParseError::IdentifierAlreadyInUse {
+            identifier: name.to_string(),
+            line_number,
+            column,
+        });
+    }
+
+    Ok(name.to_string())
+}
+
+fn expect_token(expected: Token, token_consumer: &mut TokenConsumer) -> Result<(), ParseError> {
+    match token_consumer.next() {
+        Some((token, _, _)) if token.eq(&expected) => Ok(()),
+        Some((token, line_number, column)) => Err(ParseError::UnexpectedToken {
+            token: token.clone(),
+            line_number,
+            column,
+        }),
+        None => Err(ParseError::UnexpectedEndOfTokens),
+    }
+}
+
+// Parses a non-empty, comma separated list of field names, the opening
+// parenthesis must already be consumed.
+fn parse_field_name_list(token_consumer: &mut TokenConsumer) -> Result<Vec<FieldName>, ParseError> {
+    let mut field_names = vec![];
+
+    while let Some((token, line_number, column)) = token_consumer.next() {
+        match token {
+            Token::Identifier(name) => {
+                field_names.push(FieldName::new(name.clone(), line_number, column));
+
+                match token_consumer.next() {
+                    Some((Token::Comma, _, _)) => continue,
+                    Some((Token::ParenClose, _, _)) => return Ok(field_names),
+                    Some((token, line_number, column)) => {
+                        return Err(ParseError::UnexpectedToken {
+                            token: token.clone(),
+                            line_number,
+                            column,
+                        })
+                    }
+                    None => return Err(ParseError::UnexpectedEndOfTokens),
+                }
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+        }
+    }
+
+    Err(ParseError::UnexpectedEndOfTokens)
+}
+
 fn parse_field(
     name: String,
     token_consumer: &mut TokenConsumer,
+    field_line: usize,
+    field_column: usize,
 ) -> Result<rayql::schema::Field, ParseError> {
     let data_type = get_data_type_with_span(token_consumer.next())?;
 
     let mut properties = vec![];
+    let mut map = None;
+
+    loop {
+        let (token, line_number, column) = match token_consumer.peek() {
+            Some(next) => next,
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        };
 
-    while let Some((token, line_number, column)) = token_consumer.peek() {
         match token {
             Token::Comma => {
                 token_consumer.next();
-                return Ok(rayql::schema::Field::new(
-                    name,
-                    data_type,
-                    properties,
-                    line_number,
-                    column,
-                ));
+                break;
             }
             Token::Identifier(identifier) => {
                 token_consumer.next();
                 if let Some((Token::ParenOpen, _, _)) = token_consumer.peek() {
                     token_consumer.next();
+
+                    if identifier.eq("check") {
+                        let expression = parse_expression(token_consumer)?;
+                        expect_token(Token::ParenClose, token_consumer)?;
+                        properties.push(rayql::schema::Property::Check(expression));
+                        continue;
+                    }
+
+                    if identifier.eq("generated") {
+                        properties.push(parse_generated(token_consumer)?);
+                        continue;
+                    }
+
+                    if identifier.eq("map") && map.is_none() {
+                        map = Some(parse_map(token_consumer)?);
+                        continue;
+                    }
+
                     properties.push(rayql::schema::Property::FunctionCall(parse_function_call(
                         identifier.clone(),
                         rayql::schema::FunctionCallContext::new(name.clone(), data_type.clone()),
@@ -210,15 +1104,7 @@ fn parse_field(
                     &column,
                 )?);
             }
-            Token::BraceClose => {
-                return Ok(rayql::schema::Field::new(
-                    name,
-                    data_type,
-                    properties,
-                    line_number,
-                    column,
-                ))
-            }
+            Token::BraceClose => break,
             _ => {
                 return Err(ParseError::UnexpectedToken {
                     token: token.clone(),
@@ -229,7 +1115,40 @@ fn parse_field(
         }
     }
 
-    Err(ParseError::UnexpectedEndOfTokens)
+    // function calls render the column they are declared on
+    if let Some(map) = &map {
+        for property in &mut properties {
+            if let rayql::schema::Property::FunctionCall(func) = property {
+                func.context.column_name = map.clone();
+            }
+        }
+    }
+
+    let mut field =
+        rayql::schema::Field::new(name, data_type, properties, field_line, field_column);
+    field.map = map;
+
+    Ok(field)
+}
+
+// Parses the physical name given with `map("name")`, after the opening
+// parenthesis.
+fn parse_map(token_consumer: &mut TokenConsumer) -> Result<String, ParseError> {
+    let name = match token_consumer.next() {
+        Some((Token::StringLiteral(name), _, _)) if !name.is_empty() => name.clone(),
+        Some((token, line_number, column)) => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    expect_token(Token::ParenClose, token_consumer)?;
+
+    Ok(name)
 }
 
 fn parse_function_call(
@@ -240,13 +1159,30 @@ fn parse_function_call(
     fn_call_column: usize,
 ) -> Result<rayql::schema::FunctionCall, ParseError> {
     let mut arguments: Vec<rayql::schema::Argument> = vec![];
+    let mut named_arguments: Vec<rayql::schema::NamedArgument> = vec![];
 
     while let Some((token, line_number, column)) = token_consumer.next() {
+        let (argument_name, token, line_number, column) = match token {
+            Token::Identifier(identifier)
+                if matches!(token_consumer.peek(), Some((Token::Colon, _, _))) =>
+            {
+                token_consumer.next();
+                match token_consumer.next() {
+                    Some((token, line_number, column)) => {
+                        (Some(identifier.clone()), token, line_number, column)
+                    }
+                    None => return Err(ParseError::UnexpectedEndOfTokens),
+                }
+            }
+            _ => (None, token, line_number, column),
+        };
+
         let argument = match token {
-            Token::ParenClose => {
+            Token::ParenClose if argument_name.is_none() => {
                 return Ok(rayql::schema::FunctionCall::new(
                     name,
                     arguments,
+                    named_arguments,
                     context,
                     fn_call_line,
                     fn_call_column,
@@ -329,18 +1265,24 @@ fn parse_function_call(
             }
         };
 
+        match argument_name {
+            Some(argument_name) => {
+                named_arguments.push(rayql::schema::NamedArgument::new(argument_name, argument))
+            }
+            None => arguments.push(argument),
+        }
+
         if let Some((token, line_number, column)) = token_consumer.peek() {
             match token {
                 Token::Comma => {
                     token_consumer.next();
-                    arguments.push(argument)
                 }
                 Token::ParenClose => {
                     token_consumer.next();
-                    arguments.push(argument);
                     return Ok(rayql::schema::FunctionCall::new(
                         name,
                         arguments,
+                        named_arguments,
                         context,
                         fn_call_line,
                         fn_call_column,
@@ -359,3 +1301,322 @@ fn parse_function_call(
 
     Err(ParseError::UnexpectedEndOfTokens)
 }
+
+// Parses the arguments of `generated(expression[, stored | virtual])`, the
+// opening parenthesis must already be consumed.
+fn parse_generated(
+    token_consumer: &mut TokenConsumer,
+) -> Result<rayql::schema::Property, ParseError> {
+    let expression = parse_expression(token_consumer)?;
+
+    let stored = match token_consumer.next() {
+        Some((Token::ParenClose, _, _)) => {
+            return Ok(rayql::schema::Property::Generated {
+                expression,
+                stored: false,
+            })
+        }
+        Some((Token::Comma, _, _)) => match token_consumer.next() {
+            Some((Token::Identifier(storage), _, _)) if storage.eq("stored") => true,
+            Some((Token::Identifier(storage), _, _)) if storage.eq("virtual") => false,
+            Some((token, line_number, column)) => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        },
+        Some((token, line_number, column)) => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    expect_token(Token::ParenClose, token_consumer)?;
+
+    Ok(rayql::schema::Property::Generated { expression, stored })
+}
+
+fn peek_word(token_consumer: &mut TokenConsumer, word: &str) -> bool {
+    matches!(token_consumer.peek(), Some((Token::Identifier(identifier), _, _)) if identifier.eq(word))
+}
+
+fn peek_operator(token_consumer: &mut TokenConsumer, operators: &[&str]) -> Option<BinaryOperator> {
+    match token_consumer.peek() {
+        Some((Token::Operator(operator), _, _)) if operators.contains(&operator.as_str()) => {
+            BinaryOperator::from_operator(operator)
+        }
+        _ => None,
+    }
+}
+
+fn binary_expression(left: Expression, operator: BinaryOperator, right: Expression) -> Expression {
+    let (line_number, column) = (left.line_number, left.column);
+
+    Expression::new(
+        ExpressionKind::Binary(Box::new(left), operator, Box::new(right)),
+        line_number,
+        column,
+    )
+}
+
+fn parse_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    let mut left = parse_and_expression(token_consumer)?;
+
+    while peek_word(token_consumer, "or") {
+        token_consumer.next();
+        let right = parse_and_expression(token_consumer)?;
+        left = binary_expression(left, BinaryOperator::Or, right);
+    }
+
+    Ok(left)
+}
+
+fn parse_and_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    let mut left = parse_not_expression(token_consumer)?;
+
+    while peek_word(token_consumer, "and") {
+        token_consumer.next();
+        let right = parse_not_expression(token_consumer)?;
+        left = binary_expression(left, BinaryOperator::And, right);
+    }
+
+    Ok(left)
+}
+
+fn parse_not_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    if peek_word(token_consumer, "not") {
+        let (_, line_number, column) = token_consumer.next().unwrap();
+        let operand = parse_not_expression(token_consumer)?;
+
+        return Ok(Expression::new(
+            ExpressionKind::Unary(UnaryOperator::Not, Box::new(operand)),
+            line_number,
+            column,
+        ));
+    }
+
+    parse_predicate(token_consumer)
+}
+
+// Comparisons, `[not] in (...)`, `[not] like ...` and `is [not] null`
+fn parse_predicate(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    let left = parse_additive_expression(token_consumer)?;
+    let (line_number, column) = (left.line_number, left.column);
+
+    if let Some(operator) = peek_operator(
+        token_consumer,
+        &["=", "==", "!=", "<>", "<", "<=", ">", ">="],
+    ) {
+        token_consumer.next();
+        let right = parse_additive_expression(token_consumer)?;
+        return Ok(binary_expression(left, operator, right));
+    }
+
+    if peek_word(token_consumer, "is") {
+        token_consumer.next();
+        let negated = peek_word(token_consumer, "not");
+        if negated {
+            token_consumer.next();
+        }
+        expect_token(Token::Identifier("null".to_string()), token_consumer)?;
+
+        return Ok(Expression::new(
+            ExpressionKind::IsNull {
+                expression: Box::new(left),
+                negated,
+            },
+            line_number,
+            column,
+        ));
+    }
+
+    let negated = peek_word(token_consumer, "not");
+    if negated {
+        token_consumer.next();
+    }
+
+    let kind = match token_consumer.peek() {
+        Some((Token::Identifier(word), _, _)) if word.eq("in") => {
+            token_consumer.next();
+            expect_token(Token::ParenOpen, token_consumer)?;
+            let list = parse_expression_list(token_consumer)?;
+
+            ExpressionKind::In {
+                expression: Box::new(left),
+                list,
+                negated,
+            }
+        }
+        Some((Token::Identifier(word), _, _)) if word.eq("like") => {
+            token_consumer.next();
+            let pattern = parse_additive_expression(token_consumer)?;
+
+            ExpressionKind::Like {
+                expression: Box::new(left),
+                pattern: Box::new(pattern),
+                negated,
+            }
+        }
+        Some((token, line_number, column)) if negated => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+        None if negated => return Err(ParseError::UnexpectedEndOfTokens),
+        _ => return Ok(left),
+    };
+
+    Ok(Expression::new(kind, line_number, column))
+}
+
+fn parse_additive_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    let mut left = parse_multiplicative_expression(token_consumer)?;
+
+    while let Some(operator) = peek_operator(token_consumer, &["+", "-"]) {
+        token_consumer.next();
+        let right = parse_multiplicative_expression(token_consumer)?;
+        left = binary_expression(left, operator, right);
+    }
+
+    Ok(left)
+}
+
+fn parse_multiplicative_expression(
+    token_consumer: &mut TokenConsumer,
+) -> Result<Expression, ParseError> {
+    let mut left = parse_concat_expression(token_consumer)?;
+
+    while let Some(operator) = peek_operator(token_consumer, &["*", "/", "%"]) {
+        token_consumer.next();
+        let right = parse_concat_expression(token_consumer)?;
+        left = binary_expression(left, operator, right);
+    }
+
+    Ok(left)
+}
+
+fn parse_concat_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    let mut left = parse_unary_expression(token_consumer)?;
+
+    while let Some(operator) = peek_operator(token_consumer, &["||"]) {
+        token_consumer.next();
+        let right = parse_unary_expression(token_consumer)?;
+        left = binary_expression(left, operator, right);
+    }
+
+    Ok(left)
+}
+
+fn parse_unary_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    if let Some((Token::Operator(operator), line_number, column)) = token_consumer.peek() {
+        if operator.eq("-") {
+            token_consumer.next();
+            let operand = parse_unary_expression(token_consumer)?;
+
+            return Ok(Expression::new(
+                ExpressionKind::Unary(UnaryOperator::Negate, Box::new(operand)),
+                line_number,
+                column,
+            ));
+        }
+    }
+
+    parse_primary_expression(token_consumer)
+}
+
+fn parse_primary_expression(token_consumer: &mut TokenConsumer) -> Result<Expression, ParseError> {
+    let (token, line_number, column) = match token_consumer.next() {
+        Some(next) => next,
+        None => return Err(ParseError::UnexpectedEndOfTokens),
+    };
+
+    let kind = match token {
+        Token::StringLiteral(s) => {
+            ExpressionKind::Value(rayql::value::Value::StringLiteral(s.clone()))
+        }
+        Token::Integer(i) => ExpressionKind::Value(rayql::value::Value::Integer(*i)),
+        Token::Real(r) => ExpressionKind::Value(rayql::value::Value::Real(*r)),
+        Token::Boolean(b) => ExpressionKind::Value(rayql::value::Value::Boolean(*b)),
+        Token::Identifier(identifier) if identifier.eq("null") => ExpressionKind::Null,
+        Token::Identifier(identifier) => match token_consumer.peek() {
+            Some((Token::ParenOpen, _, _)) => {
+                token_consumer.next();
+                let arguments = match token_consumer.peek() {
+                    Some((Token::ParenClose, _, _)) => {
+                        token_consumer.next();
+                        vec![]
+                    }
+                    _ => parse_expression_list(token_consumer)?,
+                };
+
+                ExpressionKind::FunctionCall(identifier.clone(), arguments)
+            }
+            _ => ExpressionKind::Field(identifier.clone()),
+        },
+        Token::Reference(entity, property) => {
+            if property.contains('.') {
+                return Err(ParseError::InvalidReference {
+                    entity: entity.to_string(),
+                    property: property.to_string(),
+                    line_number,
+                    column,
+                });
+            }
+
+            ExpressionKind::Reference(rayql::schema::Reference::new(
+                entity.clone(),
+                property.clone(),
+                line_number,
+                column,
+            ))
+        }
+        Token::ParenOpen => {
+            let expression = parse_expression(token_consumer)?;
+            expect_token(Token::ParenClose, token_consumer)?;
+            return Ok(expression);
+        }
+        _ => {
+            return Err(ParseError::UnexpectedToken {
+                token: token.clone(),
+                line_number,
+                column,
+            })
+        }
+    };
+
+    Ok(Expression::new(kind, line_number, column))
+}
+
+// Parses a comma separated list of expressions, the opening parenthesis must
+// already be consumed.
+fn parse_expression_list(
+    token_consumer: &mut TokenConsumer,
+) -> Result<Vec<Expression>, ParseError> {
+    let mut expressions = vec![];
+
+    loop {
+        expressions.push(parse_expression(token_consumer)?);
+
+        match token_consumer.next() {
+            Some((Token::Comma, _, _)) => continue,
+            Some((Token::ParenClose, _, _)) => return Ok(expressions),
+            Some((token, line_number, column)) => {
+                return Err(ParseError::UnexpectedToken {
+                    token: token.clone(),
+                    line_number,
+                    column,
+                })
+            }
+            None => return Err(ParseError::UnexpectedEndOfTokens),
+        }
+    }
+}
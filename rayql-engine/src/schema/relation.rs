@@ -0,0 +1,609 @@
+use rayql::{
+    schema::{
+        Argument, ArgumentValue, ConstraintKind, DataTypeWithSpan, Field, FieldName, FunctionCall,
+        FunctionCallContext, Model, ModelConstraint, NamedArgument, Property, Reference, Schema,
+    },
+    sql::error::ToSQLError,
+    types::DataType,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RelationKind {
+    BelongsTo,
+    HasMany,
+    ManyToMany,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForeignKey {
+    pub model: String,
+    pub field: String,
+    pub referenced_model: String,
+    pub referenced_field: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Relation {
+    pub model: String,
+    pub field: String,
+    pub kind: RelationKind,
+    pub target: String,
+    pub optional: bool,
+    pub foreign_key: ForeignKey,
+    pub join_table: Option<String>,
+    pub line_number: usize,
+    pub column: usize,
+}
+
+impl Field {
+    pub fn is_relation(&self) -> bool {
+        self.data_type.data_type.is_relation()
+    }
+
+    pub fn get_foreign_key(&self, model_name: &str) -> Option<ForeignKey> {
+        self.properties.iter().find_map(|property| match property {
+            Property::FunctionCall(func)
+                if func.name.eq("foreign_key") || func.name.eq("references") =>
+            {
+                match func.arguments.get_first().map(|argument| &argument.value) {
+                    Some(ArgumentValue::Reference(reference)) => Some(ForeignKey {
+                        model: model_name.to_string(),
+                        field: self.name.clone(),
+                        referenced_model: reference.entity.clone(),
+                        referenced_field: reference.property.clone(),
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    }
+
+    fn get_relation_call(&self) -> Option<&FunctionCall> {
+        self.properties.iter().find_map(|property| match property {
+            Property::FunctionCall(func) if func.name.eq("relation") => Some(func),
+            _ => None,
+        })
+    }
+
+    fn get_relation_field_name(&self, model_name: &str) -> Result<Option<String>, ToSQLError> {
+        let func = match self.get_relation_call() {
+            Some(func) => func,
+            None => return Ok(None),
+        };
+
+        if let Some(named_argument) = func
+            .arguments
+            .named
+            .iter()
+            .find(|named_argument| named_argument.name.ne("join_table"))
+        {
+            return Err(ToSQLError::InvalidRelation {
+                model_name: model_name.to_string(),
+                field_name: self.name.clone(),
+                reason: format!("unknown argument '{}'", named_argument.name),
+                line_number: named_argument.argument.line_number,
+                column: named_argument.argument.column,
+            });
+        }
+
+        match func.arguments.list.as_slice() {
+            [] => Ok(None),
+            [argument] => match &argument.value {
+                ArgumentValue::Identifier(field_name) => Ok(Some(field_name.clone())),
+                value => Err(ToSQLError::InvalidRelation {
+                    model_name: model_name.to_string(),
+                    field_name: self.name.clone(),
+                    reason: format!("relation expects a field name, got {:?}", value),
+                    line_number: argument.line_number,
+                    column: argument.column,
+                }),
+            },
+            _ => Err(ToSQLError::InvalidRelation {
+                model_name: model_name.to_string(),
+                field_name: self.name.clone(),
+                reason: "relation takes exactly one field name".to_string(),
+                line_number: func.line_number,
+                column: func.column,
+            }),
+        }
+    }
+
+    fn get_join_table_name(&self, model_name: &str) -> Result<Option<String>, ToSQLError> {
+        let argument = match self
+            .get_relation_call()
+            .and_then(|func| func.arguments.get_named("join_table"))
+        {
+            Some(argument) => argument,
+            None => return Ok(None),
+        };
+
+        match &argument.value {
+            ArgumentValue::Identifier(join_table) => Ok(Some(join_table.clone())),
+            value => Err(ToSQLError::InvalidRelation {
+                model_name: model_name.to_string(),
+                field_name: self.name.clone(),
+                reason: format!("join_table expects a table name, got {:?}", value),
+                line_number: argument.line_number,
+                column: argument.column,
+            }),
+        }
+    }
+}
+
+impl Model {
+    pub fn foreign_keys(&self) -> Vec<ForeignKey> {
+        self.fields
+            .iter()
+            .filter_map(|field| field.get_foreign_key(&self.name))
+            .collect()
+    }
+
+    pub fn get_primary_key_field(&self) -> Option<&Field> {
+        let mut primary_keys = self
+            .fields
+            .iter()
+            .filter(|field| field.properties.contains(&Property::PrimaryKey));
+
+        match (primary_keys.next(), primary_keys.next()) {
+            (Some(field), None) => Some(field),
+            _ => None,
+        }
+    }
+
+    fn invalid_relation(&self, field: &Field, reason: String) -> ToSQLError {
+        ToSQLError::InvalidRelation {
+            model_name: self.name.clone(),
+            field_name: field.name.clone(),
+            reason,
+            line_number: field.data_type.line_number,
+            column: field.data_type.column,
+        }
+    }
+}
+
+impl Schema {
+    pub fn relations(&self) -> Result<Vec<Relation>, ToSQLError> {
+        let mut relations = Vec::new();
+
+        for model in self.models.iter().filter(|model| !model.is_abstract) {
+            for field in model.fields.iter().filter(|field| field.is_relation()) {
+                relations.push(
+                    self.resolve_relation(model, field)
+                        .map_err(|error| error.in_file(&field.file))?,
+                );
+            }
+        }
+
+        Ok(relations)
+    }
+
+    fn resolve_relation(&self, model: &Model, field: &Field) -> Result<Relation, ToSQLError> {
+        let (target, kind, optional) = match &field.data_type.data_type {
+            DataType::Model(target) => (target, RelationKind::BelongsTo, false),
+            DataType::Optional(inner) => match inner.as_ref() {
+                DataType::Model(target) => (target, RelationKind::BelongsTo, true),
+                _ => {
+                    return Err(model
+                        .invalid_relation(field, "list relations cannot be optional".to_string()))
+                }
+            },
+            DataType::List(inner) => match inner.as_ref() {
+                DataType::Model(target) => (target, RelationKind::HasMany, false),
+                DataType::Enum(name) => {
+                    return Err(ToSQLError::ModelNotFound {
+                        model_name: name.clone(),
+                        line_number: field.data_type.line_number,
+                        column: field.data_type.column,
+                    })
+                }
+                _ => {
+                    return Err(model
+                        .invalid_relation(field, "list fields must reference a model".to_string()))
+                }
+            },
+            _ => unreachable!("field is not a relation"),
+        };
+
+        let target_model = match self.get_model(target) {
+            Some(target_model) if target_model.is_abstract => {
+                return Err(model
+                    .invalid_relation(field, format!("'{}' is abstract and has no table", target)))
+            }
+            Some(target_model) => target_model,
+            None => {
+                return Err(ToSQLError::ModelNotFound {
+                    model_name: target.clone(),
+                    line_number: field.data_type.line_number,
+                    column: field.data_type.column,
+                })
+            }
+        };
+
+        let relation_field_name = field.get_relation_field_name(&model.name)?;
+        let mut join_table = field.get_join_table_name(&model.name)?;
+        let mut kind = kind;
+
+        let foreign_key = match kind {
+            // the foreign key lives on this model, e.g. `author: user relation(author_id)`
+            RelationKind::BelongsTo => {
+                let field_name = match relation_field_name {
+                    Some(field_name) => field_name,
+                    None => {
+                        return Err(model.invalid_relation(
+                            field,
+                            format!(
+                                "relation(<field>) is required to know which field references '{}'",
+                                target
+                            ),
+                        ))
+                    }
+                };
+
+                let foreign_key_field = match model.get_field(&field_name) {
+                    Some(foreign_key_field) if !foreign_key_field.is_relation() => {
+                        foreign_key_field
+                    }
+                    _ => {
+                        return Err(ToSQLError::FieldNotFound {
+                            model_name: model.name.clone(),
+                            field_name,
+                            line_number: field.data_type.line_number,
+                            column: field.data_type.column,
+                        })
+                    }
+                };
+
+                match foreign_key_field.get_foreign_key(&model.name) {
+                    Some(foreign_key) if foreign_key.referenced_model.eq(target) => foreign_key,
+                    _ => {
+                        return Err(model.invalid_relation(
+                            field,
+                            format!(
+                                "field '{}' is not a foreign key referencing '{}'",
+                                field_name, target
+                            ),
+                        ))
+                    }
+                }
+            }
+            // the foreign key is inferred from the target model, e.g. `posts: post[]`
+            RelationKind::HasMany | RelationKind::ManyToMany => {
+                let mut candidates: Vec<ForeignKey> = target_model
+                    .foreign_keys()
+                    .into_iter()
+                    .filter(|foreign_key| foreign_key.referenced_model.eq(&model.name))
+                    .filter(|foreign_key| match &relation_field_name {
+                        Some(field_name) => foreign_key.field.eq(field_name),
+                        None => true,
+                    })
+                    .collect();
+
+                let back_reference = if candidates.is_empty() && relation_field_name.is_none() {
+                    self.back_reference(model, field, target_model)?
+                } else {
+                    None
+                };
+
+                match (candidates.len(), back_reference) {
+                    (1, _) => candidates.remove(0),
+                    // both sides hold a list of the other one, e.g. `tags: tag[]` and `posts: post[]`
+                    (0, Some(back_reference))
+                        if relation_field_name.is_none() && target_model.name.ne(&model.name) =>
+                    {
+                        let name = self.join_table_name(model, field, target_model, back_reference)?;
+                        let primary_key = match model.get_primary_key_field() {
+                            Some(primary_key) => primary_key,
+                            None => {
+                                return Err(model.invalid_relation(
+                                    field,
+                                    format!(
+                                        "many-to-many relations require '{}' to have a single primary key",
+                                        model.name
+                                    ),
+                                ))
+                            }
+                        };
+
+                        kind = RelationKind::ManyToMany;
+                        join_table = Some(name.clone());
+
+                        ForeignKey {
+                            model: name,
+                            field: format!("{}_{}", model.name, primary_key.name),
+                            referenced_model: model.name.clone(),
+                            referenced_field: primary_key.name.clone(),
+                        }
+                    }
+                    (0, _) => {
+                        return Err(model.invalid_relation(
+                            field,
+                            format!(
+                                "no foreign key on '{}' references '{}'",
+                                target, model.name
+                            ),
+                        ))
+                    }
+                    _ => {
+                        return Err(model.invalid_relation(
+                            field,
+                            format!(
+                                "'{}' has more than one foreign key referencing '{}', use relation(<field>) to pick one",
+                                target, model.name
+                            ),
+                        ))
+                    }
+                }
+            }
+        };
+
+        if join_table.is_some() && kind.ne(&RelationKind::ManyToMany) {
+            return Err(model.invalid_relation(
+                field,
+                "join_table can only be used on many-to-many relations".to_string(),
+            ));
+        }
+
+        Ok(Relation {
+            model: model.name.clone(),
+            field: field.name.clone(),
+            kind,
+            target: target.clone(),
+            optional,
+            foreign_key,
+            join_table,
+            line_number: field.data_type.line_number,
+            column: field.data_type.column,
+        })
+    }
+
+    // The list of `model` on `target_model` pairing with `field` in a
+    // many-to-many relation. When several lists connect the two models, both
+    // sides of each pair have to name the same join table.
+    fn back_reference<'a>(
+        &self,
+        model: &Model,
+        field: &Field,
+        target_model: &'a Model,
+    ) -> Result<Option<&'a Field>, ToSQLError> {
+        let is_list_of = |field: &Field, model_name: &str| {
+            field.data_type.data_type
+                == DataType::List(Box::new(DataType::Model(model_name.to_string())))
+        };
+
+        let back_references: Vec<&Field> = target_model
+            .fields
+            .iter()
+            .filter(|target_field| is_list_of(target_field, &model.name))
+            .collect();
+        let is_ambiguous = back_references.len() > 1
+            || model
+                .fields
+                .iter()
+                .filter(|model_field| is_list_of(model_field, &target_model.name))
+                .count()
+                > 1;
+
+        if !is_ambiguous {
+            return Ok(back_references.first().copied());
+        }
+
+        let join_table = match field.get_join_table_name(&model.name)? {
+            Some(join_table) => join_table,
+            None => {
+                return Err(model.invalid_relation(
+                    field,
+                    format!(
+                        "'{}' and '{}' are related by more than one list, use relation(join_table: <name>) on both sides",
+                        model.name, target_model.name
+                    ),
+                ))
+            }
+        };
+
+        let mut matching = Vec::new();
+        for back_reference in back_references {
+            if back_reference
+                .get_join_table_name(&target_model.name)?
+                .is_some_and(|name| name.eq(&join_table))
+            {
+                matching.push(back_reference);
+            }
+        }
+
+        match matching.as_slice() {
+            [back_reference] => Ok(Some(back_reference)),
+            [] => Err(model.invalid_relation(
+                field,
+                format!(
+                    "no list of '{}' on '{}' declares join_table '{}'",
+                    model.name, target_model.name, join_table
+                ),
+            )),
+            _ => Err(model.invalid_relation(
+                field,
+                format!(
+                    "more than one list of '{}' on '{}' declares join_table '{}'",
+                    model.name, target_model.name, join_table
+                ),
+            )),
+        }
+    }
+
+    fn join_table_name(
+        &self,
+        model: &Model,
+        field: &Field,
+        target_model: &Model,
+        back_reference: &Field,
+    ) -> Result<String, ToSQLError> {
+        let name = match (
+            field.get_join_table_name(&model.name)?,
+            back_reference.get_join_table_name(&target_model.name)?,
+        ) {
+            (Some(name), Some(other_name)) if name.ne(&other_name) => {
+                return Err(model.invalid_relation(
+                    field,
+                    format!(
+                        "join table '{}' does not match '{}' declared on '{}'",
+                        name, other_name, target_model.name
+                    ),
+                ))
+            }
+            (Some(name), _) | (None, Some(name)) => name,
+            (None, None) => {
+                let mut model_names = [model.name.as_str(), target_model.name.as_str()];
+                model_names.sort();
+                model_names.join("_")
+            }
+        };
+
+        if self.get_model(&name).is_some() {
+            return Err(model.invalid_relation(
+                field,
+                format!("join table '{}' is already declared as a model", name),
+            ));
+        }
+
+        Ok(name)
+    }
+
+    // Synthesises the tables backing many-to-many relations, each one has a
+    // composite primary key and a cascading foreign key to both sides.
+    pub fn join_tables(&self) -> Result<Vec<Model>, ToSQLError> {
+        let mut join_tables: Vec<Model> = Vec::new();
+        let relations = self.relations()?;
+
+        for relation in &relations {
+            let join_table_name = match &relation.join_table {
+                Some(join_table_name) => join_table_name,
+                None => continue,
+            };
+
+            let field = self.join_table_field(relation)?;
+            let field_name =
+                FieldName::new(field.name.clone(), relation.line_number, relation.column);
+
+            match join_tables
+                .iter_mut()
+                .find(|join_table| join_table.name.eq(join_table_name))
+            {
+                Some(join_table) => {
+                    if join_table.get_field(&field.name).is_some() {
+                        continue;
+                    }
+                    if join_table.fields.len() == 2 {
+                        return Err(self.invalid_join_table(
+                            relation,
+                            format!(
+                                "join table '{}' is used by another relation",
+                                join_table_name
+                            ),
+                        ));
+                    }
+                    join_table.fields.push(field);
+                    join_table.constraints[0].fields.push(field_name);
+                }
+                None => join_tables.push(Model::new(
+                    join_table_name.clone(),
+                    vec![field],
+                    vec![ModelConstraint::new(
+                        ConstraintKind::PrimaryKey,
+                        vec![field_name],
+                        relation.line_number,
+                        relation.column,
+                    )],
+                    relation.line_number,
+                    relation.column,
+                )),
+            }
+        }
+
+        // each side of the relation adds the column referencing its model
+        for join_table in &join_tables {
+            if join_table.fields.len() != 2 {
+                let relation = relations
+                    .iter()
+                    .find(|relation| relation.join_table.as_ref().eq(&Some(&join_table.name)))
+                    .expect("join tables are created from relations");
+
+                return Err(self.invalid_join_table(
+                    relation,
+                    format!(
+                        "join table '{}' needs a column for both '{}' and '{}'",
+                        join_table.name, relation.model, relation.target
+                    ),
+                ));
+            }
+        }
+
+        Ok(join_tables)
+    }
+
+    fn invalid_join_table(&self, relation: &Relation, reason: String) -> ToSQLError {
+        ToSQLError::InvalidRelation {
+            model_name: relation.model.clone(),
+            field_name: relation.field.clone(),
+            reason,
+            line_number: relation.line_number,
+            column: relation.column,
+        }
+    }
+
+    fn join_table_field(&self, relation: &Relation) -> Result<Field, ToSQLError> {
+        let foreign_key = &relation.foreign_key;
+        let (line_number, column) = (relation.line_number, relation.column);
+
+        let data_type = match self
+            .get_model(&foreign_key.referenced_model)
+            .and_then(|model| model.get_field(&foreign_key.referenced_field))
+        {
+            Some(field) => match &field.data_type.data_type {
+                DataType::Optional(inner) => inner.as_ref().clone(),
+                data_type => data_type.clone(),
+            },
+            None => {
+                return Err(ToSQLError::FieldNotFound {
+                    model_name: foreign_key.referenced_model.clone(),
+                    field_name: foreign_key.referenced_field.clone(),
+                    line_number,
+                    column,
+                })
+            }
+        };
+        let data_type = DataTypeWithSpan::new(data_type, line_number, column);
+
+        let foreign_key_call = FunctionCall::new(
+            "foreign_key".to_string(),
+            vec![Argument::new(
+                ArgumentValue::Reference(Reference::new(
+                    foreign_key.referenced_model.clone(),
+                    foreign_key.referenced_field.clone(),
+                    line_number,
+                    column,
+                )),
+                line_number,
+                column,
+            )],
+            vec![NamedArgument::new(
+                "on_delete".to_string(),
+                Argument::new(
+                    ArgumentValue::Identifier("cascade".to_string()),
+                    line_number,
+                    column,
+                ),
+            )],
+            FunctionCallContext::new(foreign_key.field.clone(), data_type.clone()),
+            line_number,
+            column,
+        );
+
+        Ok(Field::new(
+            foreign_key.field.clone(),
+            data_type,
+            vec![Property::FunctionCall(foreign_key_call)],
+            line_number,
+            column,
+        ))
+    }
+}
@@ -16,6 +16,11 @@ pub enum Token {
     Colon,
     Comma,
     Optional(Box<Token>),
+    List(Box<Token>),
+    DocComment(String),
+    Operator(String),
+    JsonShape(String),
+    Decimal(u32, u32),
 }
 
 impl std::fmt::Display for Token {
@@ -35,6 +40,11 @@ impl std::fmt::Display for Token {
             Token::Colon => write!(f, ":"),
             Token::Comma => write!(f, ","),
             Token::Optional(token) => write!(f, "Optional {}", token),
+            Token::List(token) => write!(f, "List {}", token),
+            Token::DocComment(doc) => write!(f, "DocComment: {}", doc),
+            Token::Operator(operator) => write!(f, "Operator: {}", operator),
+            Token::JsonShape(shape) => write!(f, "JsonShape: {}", shape),
+            Token::Decimal(precision, scale) => write!(f, "Decimal: {}, {}", precision, scale),
         }
     }
 }
@@ -56,6 +66,13 @@ impl Token {
             Token::Colon => 1,
             Token::Comma => 1,
             Token::Optional(token) => token.len() + 9, // +9 for "Optional " prefix
+            Token::List(token) => token.len() + 5,     // +5 for "List " prefix
+            Token::DocComment(doc) => doc.len() + 2,   // +2 for the "##" prefix
+            Token::Operator(operator) => operator.len(),
+            Token::JsonShape(shape) => shape.len() + 6, // +6 for "json<" and ">"
+            Token::Decimal(precision, scale) => {
+                precision.to_string().len() + scale.to_string().len() + 10 // +10 for "decimal(", "," and ")"
+            }
         }
     }
 }
@@ -65,6 +82,8 @@ pub enum Keyword {
     Model,
     Enum,
     Index,
+    Import,
+    Abstract,
     String,
     Integer,
     Real,
@@ -81,6 +100,8 @@ pub fn get_keyword(token_str: &str) -> Option<Keyword> {
         "model" => Some(Keyword::Model),
         "enum" => Some(Keyword::Enum),
         "index" => Some(Keyword::Index),
+        "import" => Some(Keyword::Import),
+        "abstract" => Some(Keyword::Abstract),
         "str" => Some(Keyword::String),
         "int" => Some(Keyword::Integer),
         "real" => Some(Keyword::Real),
@@ -98,9 +119,30 @@ pub fn is_comment(line: &str) -> bool {
     line.trim().starts_with('#')
 }
 
+pub fn get_doc_comment(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix("##")
+        .map(|doc| doc.strip_prefix(' ').unwrap_or(doc).trim_end())
+}
+
 pub fn tokenize(input: &str) -> Result<Vec<(Token, usize, usize)>, TokenizationError> {
     let mut tokens = Vec::new();
     for (line_num, line) in input.lines().enumerate() {
+        if let Some(doc) = get_doc_comment(line) {
+            let column = line.len() - line.trim_start().len() + 1;
+
+            // consecutive doc comment lines are merged into a single token
+            match tokens.last_mut() {
+                Some((Token::DocComment(previous), _, _)) => {
+                    previous.push('\n');
+                    previous.push_str(doc);
+                }
+                _ => tokens.push((Token::DocComment(doc.to_string()), line_num + 1, column)),
+            }
+
+            continue;
+        }
+
         if is_comment(line) {
             continue;
         }
@@ -116,6 +158,7 @@ pub fn tokenize_line(
 ) -> Result<Vec<(Token, usize, usize)>, TokenizationError> {
     let mut tokens = Vec::new();
     let mut in_string_literal = false;
+    let mut string_delimiter = '\'';
     let mut is_escaped = false;
 
     let mut buffer = String::new();
@@ -147,7 +190,7 @@ pub fn tokenize_line(
                 is_escaped = false;
             } else if ch == '\\' {
                 is_escaped = true;
-            } else if ch == '\'' {
+            } else if ch == string_delimiter {
                 in_string_literal = false;
                 tokens.push((
                     Token::StringLiteral(buffer.clone()),
@@ -169,7 +212,10 @@ pub fn tokenize_line(
             }
         } else {
             match ch {
-                '\'' if buffer.is_empty() => in_string_literal = true,
+                '\'' | '"' if buffer.is_empty() => {
+                    in_string_literal = true;
+                    string_delimiter = ch;
+                }
                 '_' if !buffer.is_empty() => {
                     if !is_valid_identifier(&buffer) {
                         return Err(TokenizationError::UnexpectedCharacter {
@@ -203,35 +249,17 @@ pub fn tokenize_line(
                     }
                     None => return Err(TokenizationError::UnexpectedEndOfInput),
                 },
-                '-' if buffer.is_empty() => {
-                    if let Some(next_char) = chars.peek() {
-                        if next_char.is_numeric() || next_char.eq(&'.') {
-                            buffer.push(ch);
-                        } else {
-                            return Err(TokenizationError::UnexpectedCharacter {
-                                char: ch,
-                                line: line_number,
-                                column,
-                            });
-                        }
-                    } else {
-                        return Err(TokenizationError::UnexpectedEndOfInput);
-                    }
+                '-' if buffer.is_empty()
+                    && !follows_operand(&tokens)
+                    && matches!(chars.peek(), Some(next_char) if next_char.is_numeric() || next_char.eq(&'.')) =>
+                {
+                    buffer.push(ch);
                 }
-                '-' if buffer.ends_with('e') => {
-                    if let Some(next_char) = chars.peek() {
-                        if next_char.is_numeric() {
-                            buffer.push(ch);
-                        } else {
-                            return Err(TokenizationError::UnexpectedCharacter {
-                                char: ch,
-                                line: line_number,
-                                column,
-                            });
-                        }
-                    } else {
-                        return Err(TokenizationError::UnexpectedEndOfInput);
-                    }
+                '-' if buffer.ends_with('e')
+                    && buffer.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+                    && matches!(chars.peek(), Some(next_char) if next_char.is_numeric()) =>
+                {
+                    buffer.push(ch);
                 }
                 '?' if !buffer.is_empty() => {
                     let token = get_token(&buffer, line_number, column)?;
@@ -242,6 +270,80 @@ pub fn tokenize_line(
                     ));
                     buffer.clear();
                 }
+                '[' if !buffer.is_empty() => match chars.next() {
+                    Some(']') => {
+                        column += 1;
+                        let token = get_token(&buffer, line_number, column)?;
+                        tokens.push((
+                            Token::List(Box::new(token)),
+                            line_number,
+                            column - buffer.len() - 1,
+                        ));
+                        buffer.clear();
+                    }
+                    Some(next_char) => {
+                        return Err(TokenizationError::UnexpectedCharacter {
+                            char: next_char,
+                            line: line_number,
+                            column: column + 1,
+                        })
+                    }
+                    None => return Err(TokenizationError::UnexpectedEndOfInput),
+                },
+                // `json<shape>` is kept as a single token
+                '<' if buffer.eq("json") => {
+                    buffer.push(ch);
+                    loop {
+                        match chars.next() {
+                            Some('>') => {
+                                column += 1;
+                                buffer.push('>');
+                                break;
+                            }
+                            Some(next_char) if next_char.is_alphanumeric() || next_char == '_' => {
+                                column += 1;
+                                buffer.push(next_char);
+                            }
+                            Some(next_char) => {
+                                return Err(TokenizationError::UnexpectedCharacter {
+                                    char: next_char,
+                                    line: line_number,
+                                    column: column + 1,
+                                })
+                            }
+                            None => return Err(TokenizationError::UnexpectedEndOfInput),
+                        }
+                    }
+                }
+                // `decimal(precision, scale)` is kept as a single token
+                '(' if buffer.eq("decimal") => {
+                    buffer.push(ch);
+                    loop {
+                        match chars.next() {
+                            Some(')') => {
+                                column += 1;
+                                buffer.push(')');
+                                break;
+                            }
+                            Some(next_char)
+                                if next_char.is_ascii_digit()
+                                    || next_char == ','
+                                    || next_char == ' ' =>
+                            {
+                                column += 1;
+                                buffer.push(next_char);
+                            }
+                            Some(next_char) => {
+                                return Err(TokenizationError::UnexpectedCharacter {
+                                    char: next_char,
+                                    line: line_number,
+                                    column: column + 1,
+                                })
+                            }
+                            None => return Err(TokenizationError::UnexpectedEndOfInput),
+                        }
+                    }
+                }
                 ch if ch.is_alphanumeric() => buffer.push(ch),
                 _ => {
                     if !buffer.is_empty() {
@@ -260,6 +362,34 @@ pub fn tokenize_line(
                         '}' => Token::BraceClose,
                         '(' => Token::ParenOpen,
                         ')' => Token::ParenClose,
+                        '+' | '-' | '*' | '/' | '%' => Token::Operator(ch.to_string()),
+                        '=' | '<' | '>' | '!' | '|' => {
+                            let operator = match (ch, chars.peek()) {
+                                ('=', Some('='))
+                                | ('<', Some('=' | '>'))
+                                | ('>', Some('='))
+                                | ('!', Some('='))
+                                | ('|', Some('|')) => {
+                                    column += 1;
+                                    format!("{}{}", ch, chars.next().unwrap())
+                                }
+                                ('=' | '<' | '>', _) => ch.to_string(),
+                                _ => {
+                                    return Err(TokenizationError::UnexpectedCharacter {
+                                        char: ch,
+                                        line: line_number,
+                                        column,
+                                    })
+                                }
+                            };
+
+                            tokens.push((
+                                Token::Operator(operator.clone()),
+                                line_number,
+                                column + 1 - operator.len(),
+                            ));
+                            continue;
+                        }
                         _ => {
                             return Err(TokenizationError::UnexpectedCharacter {
                                 char: ch,
@@ -302,6 +432,34 @@ pub fn get_token(
         return Ok(Token::Keyword(keyword));
     }
 
+    if let Some(shape) = token_str
+        .strip_prefix("json<")
+        .and_then(|shape| shape.strip_suffix('>'))
+    {
+        return Ok(Token::JsonShape(shape.to_string()));
+    }
+
+    if let Some(arguments) = token_str
+        .strip_prefix("decimal(")
+        .and_then(|arguments| arguments.strip_suffix(')'))
+    {
+        return match arguments.split_once(',') {
+            Some((precision, scale)) => match (precision.trim().parse(), scale.trim().parse()) {
+                (Ok(precision), Ok(scale)) => Ok(Token::Decimal(precision, scale)),
+                _ => Err(TokenizationError::UnexpectedCharacter {
+                    char: ',',
+                    line: line_number,
+                    column,
+                }),
+            },
+            None => Err(TokenizationError::UnexpectedCharacter {
+                char: ')',
+                line: line_number,
+                column,
+            }),
+        };
+    }
+
     if let Ok(boolean) = token_str.parse::<bool>() {
         return Ok(Token::Boolean(boolean));
     }
@@ -343,6 +501,25 @@ pub fn get_token(
     }
 }
 
+// Whether a `-` following the last token is a binary operator rather than the
+// sign of a number literal.
+fn follows_operand(tokens: &[(Token, usize, usize)]) -> bool {
+    matches!(
+        tokens.last(),
+        Some((
+            Token::Identifier(_)
+                | Token::Reference(_, _)
+                | Token::StringLiteral(_)
+                | Token::Integer(_)
+                | Token::Real(_)
+                | Token::Boolean(_)
+                | Token::ParenClose,
+            _,
+            _
+        ))
+    )
+}
+
 fn is_valid_identifier(identifier: &str) -> bool {
     if identifier.chars().next().unwrap().is_ascii_digit() {
         return false;
@@ -1,5 +1,5 @@
 use rayql::schema::{
-    error::ParseError,
+    error::{MixinFieldClash, ParseError},
     tokenizer::{Keyword, Token},
     DataTypeWithSpan,
 };
@@ -22,7 +22,33 @@ pub(crate) fn get_data_type_with_span(
                 let inner_data_type = get_data_type_with_span(Some((token, line_number, column)))?;
                 rayql::types::DataType::Optional(Box::new(inner_data_type.data_type))
             }
-            Token::Identifier(identifier) => rayql::types::DataType::Enum(identifier.clone()),
+            Token::List(token) => {
+                let inner_data_type = get_data_type_with_span(Some((token, line_number, column)))?;
+                rayql::types::DataType::List(Box::new(inner_data_type.data_type))
+            }
+            Token::JsonShape(shape) => rayql::types::DataType::Json(Some(shape.clone())),
+            Token::Decimal(precision, scale) if *precision == 0 || scale > precision => {
+                return Err(ParseError::InvalidDataType {
+                    data_type: format!("decimal({}, {})", precision, scale),
+                    reason: "the precision must be at least 1 and not less than the scale"
+                        .to_string(),
+                    line_number,
+                    column,
+                })
+            }
+            Token::Decimal(precision, scale) => rayql::types::DataType::Decimal {
+                precision: *precision,
+                scale: *scale,
+            },
+            // `date`, `time`, `uuid` and `json` are not reserved so they stay usable as field
+            // names
+            Token::Identifier(identifier) => match identifier.as_str() {
+                "json" => rayql::types::DataType::Json(None),
+                "date" => rayql::types::DataType::Date,
+                "time" => rayql::types::DataType::Time,
+                "uuid" => rayql::types::DataType::Uuid,
+                _ => rayql::types::DataType::Enum(identifier.clone()),
+            },
             _ => {
                 return Err(ParseError::UnexpectedToken {
                     token: token.clone(),
@@ -43,6 +69,23 @@ pub(crate) fn get_data_type_with_span(
 pub(crate) fn get_model_or_enum_name(
     tokens_iter: &mut std::iter::Peekable<std::slice::Iter<(Token, usize, usize)>>,
     identifiers: &mut std::collections::HashSet<String>,
+) -> Result<String, ParseError> {
+    let name = get_declaration_name(tokens_iter, identifiers)?;
+
+    match tokens_iter.next() {
+        Some((Token::BraceOpen, _, _)) => Ok(name),
+        Some((token, line_number, column)) => Err(ParseError::UnexpectedToken {
+            token: token.clone(),
+            line_number: *line_number,
+            column: *column,
+        }),
+        None => Err(ParseError::UnexpectedEndOfTokens),
+    }
+}
+
+pub(crate) fn get_declaration_name(
+    tokens_iter: &mut std::iter::Peekable<std::slice::Iter<(Token, usize, usize)>>,
+    identifiers: &mut std::collections::HashSet<String>,
 ) -> Result<String, ParseError> {
     let name = match tokens_iter.next() {
         Some((Token::Identifier(name), line_number, column)) => {
@@ -68,15 +111,7 @@ pub(crate) fn get_model_or_enum_name(
 
     identifiers.insert(name.clone());
 
-    match tokens_iter.next() {
-        Some((Token::BraceOpen, _, _)) => Ok(name),
-        Some((token, line_number, column)) => Err(ParseError::UnexpectedToken {
-            token: token.clone(),
-            line_number: *line_number,
-            column: *column,
-        }),
-        None => Err(ParseError::UnexpectedEndOfTokens),
-    }
+    Ok(name)
 }
 
 pub(crate) fn keyword_to_property_value(
@@ -95,3 +130,210 @@ pub(crate) fn keyword_to_property_value(
         }),
     }
 }
+
+// Splices the fields of every `use mixin` into the model, at the position the
+// mixin was used.
+pub(crate) fn resolve_mixins(
+    models: &mut [rayql::schema::Model],
+    mixins: &[rayql::schema::Mixin],
+) -> Result<(), ParseError> {
+    for model in models.iter_mut() {
+        resolve_model_mixins(model, mixins)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn resolve_model_mixins(
+    model: &mut rayql::schema::Model,
+    mixins: &[rayql::schema::Mixin],
+) -> Result<(), ParseError> {
+    let mut offset = 0;
+
+    for mixin_use in &model.mixins {
+        let mixin = match mixins.iter().find(|mixin| mixin.name.eq(&mixin_use.name)) {
+            Some(mixin) => mixin,
+            None => {
+                return Err(ParseError::MixinNotFound {
+                    mixin: mixin_use.name.clone(),
+                    line_number: mixin_use.line_number,
+                    column: mixin_use.column,
+                })
+            }
+        };
+
+        // reported at the `use`, the mixin and the existing field can be
+        // declared in other files
+        let other_file = |file: &Option<String>| file.clone().filter(|_| model.file.ne(file));
+        for field in &mixin.fields {
+            if let Some(existing) = model.get_field(&field.name) {
+                return Err(ParseError::MixinFieldAlreadyExistsOnModel(Box::new(
+                    MixinFieldClash {
+                        field: field.name.clone(),
+                        mixin: mixin.name.clone(),
+                        model: model.name.clone(),
+                        line_number: mixin_use.line_number,
+                        column: mixin_use.column,
+                        mixin_line_number: field.line_number,
+                        mixin_column: field.column,
+                        mixin_file: other_file(&field.file),
+                        other_line_number: existing.line_number,
+                        other_column: existing.column,
+                        other_file: other_file(&existing.file),
+                    },
+                )));
+            }
+        }
+
+        let position = mixin_use.position + offset;
+        model
+            .fields
+            .splice(position..position, mixin.fields.iter().cloned());
+        offset += mixin.fields.len();
+    }
+
+    Ok(())
+}
+
+// Copies the fields and constraints of parent models into the models
+// extending them. A field redeclared by the child replaces the inherited one.
+pub(crate) fn resolve_inheritance(models: &mut [rayql::schema::Model]) -> Result<(), ParseError> {
+    let declarations = models.to_vec();
+
+    for model in models.iter_mut() {
+        resolve_model_inheritance(model, &declarations)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn resolve_model_inheritance(
+    model: &mut rayql::schema::Model,
+    declarations: &[rayql::schema::Model],
+) -> Result<(), ParseError> {
+    let (fields, constraints) = inherited_declarations(model, declarations, &mut vec![])?;
+    model.fields = fields;
+    model.constraints = constraints;
+
+    Ok(())
+}
+
+fn inherited_declarations(
+    model: &rayql::schema::Model,
+    declarations: &[rayql::schema::Model],
+    chain: &mut Vec<String>,
+) -> Result<
+    (
+        Vec<rayql::schema::Field>,
+        Vec<rayql::schema::ModelConstraint>,
+    ),
+    ParseError,
+> {
+    let extends = match &model.extends {
+        Some(extends) => extends,
+        None => return Ok((model.fields.clone(), model.constraints.clone())),
+    };
+
+    if chain.contains(&model.name) {
+        return Err(ParseError::InheritanceCycle {
+            model: model.name.clone(),
+            line_number: extends.line_number,
+            column: extends.column,
+        });
+    }
+    chain.push(model.name.clone());
+
+    let parent = match declarations
+        .iter()
+        .find(|declaration| declaration.name.eq(&extends.model))
+    {
+        Some(parent) => parent,
+        None => {
+            return Err(ParseError::ModelNotFound {
+                model: extends.model.clone(),
+                line_number: extends.line_number,
+                column: extends.column,
+            })
+        }
+    };
+
+    let (mut fields, mut constraints) = inherited_declarations(parent, declarations, chain)?;
+
+    for field in &model.fields {
+        match fields
+            .iter_mut()
+            .find(|inherited| inherited.name.eq(&field.name))
+        {
+            Some(inherited) => *inherited = field.clone(),
+            None => fields.push(field.clone()),
+        }
+    }
+    constraints.extend(model.constraints.iter().cloned());
+
+    Ok((fields, constraints))
+}
+
+// Adds `deleted_at` to soft delete models which do not declare it, once the
+// fields of mixins and parent models are known.
+pub(crate) fn resolve_soft_delete(models: &mut [rayql::schema::Model]) {
+    for model in models.iter_mut() {
+        let option = match model.get_option(&rayql::schema::ModelOptionKind::SoftDelete) {
+            Some(option) => option.clone(),
+            None => continue,
+        };
+
+        if model.get_field(rayql::schema::SOFT_DELETE_FIELD).is_none() {
+            let mut field = rayql::schema::Field::new(
+                rayql::schema::SOFT_DELETE_FIELD.to_string(),
+                rayql::schema::DataTypeWithSpan::new(
+                    rayql::types::DataType::Optional(Box::new(rayql::types::DataType::Timestamp)),
+                    option.line_number,
+                    option.column,
+                ),
+                vec![],
+                option.line_number,
+                option.column,
+            );
+            field.file = model.file.clone();
+            model.fields.push(field);
+        }
+    }
+}
+
+// Identifiers used as data types are parsed as enums, the ones naming a model
+// are turned into relations once every declaration is known.
+pub(crate) fn resolve_model_types(models: &mut [rayql::schema::Model]) {
+    let model_names: std::collections::HashSet<String> =
+        models.iter().map(|model| model.name.clone()).collect();
+
+    for model in models.iter_mut() {
+        for field in model.fields.iter_mut() {
+            field.data_type.data_type =
+                resolve_model_type(field.data_type.data_type.clone(), &model_names);
+
+            for property in field.properties.iter_mut() {
+                if let rayql::schema::Property::FunctionCall(func) = property {
+                    func.context.property_data_type = field.data_type.clone();
+                }
+            }
+        }
+    }
+}
+
+fn resolve_model_type(
+    data_type: rayql::types::DataType,
+    model_names: &std::collections::HashSet<String>,
+) -> rayql::types::DataType {
+    match data_type {
+        rayql::types::DataType::Enum(name) if model_names.contains(&name) => {
+            rayql::types::DataType::Model(name)
+        }
+        rayql::types::DataType::Optional(inner) => {
+            rayql::types::DataType::Optional(Box::new(resolve_model_type(*inner, model_names)))
+        }
+        rayql::types::DataType::List(inner) => {
+            rayql::types::DataType::List(Box::new(resolve_model_type(*inner, model_names)))
+        }
+        data_type => data_type,
+    }
+}
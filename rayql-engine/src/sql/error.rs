@@ -24,6 +24,11 @@ pub enum ToSQLError {
         line_number: usize,
         column: usize,
     },
+    StructNotFound {
+        struct_name: String,
+        line_number: usize,
+        column: usize,
+    },
     FieldNotFound {
         model_name: String,
         field_name: String,
@@ -41,11 +46,87 @@ pub enum ToSQLError {
         line_number: usize,
         column: usize,
     },
+    InvalidRelation {
+        model_name: String,
+        field_name: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidExpression {
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidProperty {
+        model_name: String,
+        field_name: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    MultiplePrimaryKeys {
+        model_name: String,
+        line_number: usize,
+        column: usize,
+    },
+    AbstractModel {
+        model_name: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidModelOption {
+        model_name: String,
+        option: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidIndex {
+        index_name: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidView {
+        view_name: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidSearch {
+        model_name: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
+    InvalidSeed {
+        model_name: String,
+        reason: String,
+        line_number: usize,
+        column: usize,
+    },
     FunctionError {
         source: rayql::sql::error::FunctionError,
         line_number: usize,
         column: usize,
     },
+    InFile {
+        file: String,
+        source: Box<ToSQLError>,
+    },
+}
+
+impl ToSQLError {
+    pub fn in_file(self, file: &Option<String>) -> ToSQLError {
+        match (file, self) {
+            (_, error @ ToSQLError::InFile { .. }) | (None, error) => error,
+            (Some(file), error) => ToSQLError::InFile {
+                file: file.clone(),
+                source: Box::new(error),
+            },
+        }
+    }
 }
 
 impl fmt::Display for ToSQLError {
@@ -97,6 +178,17 @@ impl fmt::Display for ToSQLError {
                     model_name
                 )
             }
+            ToSQLError::StructNotFound {
+                struct_name,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Struct not found: {} at line {line_number}, column {column}",
+                    struct_name
+                )
+            }
             ToSQLError::FieldNotFound {
                 model_name,
                 field_name,
@@ -106,7 +198,7 @@ impl fmt::Display for ToSQLError {
                 write!(
                     f,
                     "Field '{}' does not exists on model '{}': line {line_number}, column {column}",
-                    model_name, field_name
+                    field_name, model_name
                 )
             }
             ToSQLError::VariantNotFound {
@@ -118,7 +210,7 @@ impl fmt::Display for ToSQLError {
                 write!(
                     f,
                     "Variant '{}' does not exists on enum '{}': line {line_number}, column {column}",
-                    enum_name, variant
+                    variant, enum_name
                 )
             }
             ToSQLError::ConversionError {
@@ -132,6 +224,126 @@ impl fmt::Display for ToSQLError {
                     reason
                 )
             }
+            ToSQLError::InvalidRelation {
+                model_name,
+                field_name,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid relation '{}' on model '{}': {} at line {line_number}, column {column}",
+                    field_name, model_name, reason
+                )
+            }
+            ToSQLError::InvalidExpression {
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid expression: {} at line {line_number}, column {column}",
+                    reason
+                )
+            }
+            ToSQLError::InvalidProperty {
+                model_name,
+                field_name,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid field '{}' on model '{}': {} at line {line_number}, column {column}",
+                    field_name, model_name, reason
+                )
+            }
+            ToSQLError::MultiplePrimaryKeys {
+                model_name,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Model '{}' cannot have more than one primary key: line {line_number}, column {column}",
+                    model_name
+                )
+            }
+            ToSQLError::AbstractModel {
+                model_name,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Model '{}' is abstract and has no table: line {line_number}, column {column}",
+                    model_name
+                )
+            }
+            ToSQLError::InvalidModelOption {
+                model_name,
+                option,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid option '{}' on model '{}': {} at line {line_number}, column {column}",
+                    option, model_name, reason
+                )
+            }
+            ToSQLError::InvalidIndex {
+                index_name,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid index '{}': {} at line {line_number}, column {column}",
+                    index_name, reason
+                )
+            }
+            ToSQLError::InvalidView {
+                view_name,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid view '{}': {} at line {line_number}, column {column}",
+                    view_name, reason
+                )
+            }
+            ToSQLError::InvalidSearch {
+                model_name,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid search for model '{}': {} at line {line_number}, column {column}",
+                    model_name, reason
+                )
+            }
+            ToSQLError::InvalidSeed {
+                model_name,
+                reason,
+                line_number,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Invalid seed for model '{}': {} at line {line_number}, column {column}",
+                    model_name, reason
+                )
+            }
             ToSQLError::FunctionError {
                 source,
                 line_number,
@@ -143,6 +355,7 @@ impl fmt::Display for ToSQLError {
                     source
                 )
             }
+            ToSQLError::InFile { file, source } => write!(f, "{}: {}", file, source),
         }
     }
 }
@@ -155,6 +368,7 @@ pub enum FunctionError {
     MissingArgument,
     ExpectsExactlyOneArgument(String),
     UndefinedFunction(String),
+    UnknownNamedArgument(String, String),
 }
 
 impl std::fmt::Display for FunctionError {
@@ -172,6 +386,9 @@ impl std::fmt::Display for FunctionError {
             FunctionError::UndefinedFunction(func) => {
                 write!(f, "Undefined function called '{func}'")
             }
+            FunctionError::UnknownNamedArgument(func, argument) => {
+                write!(f, "Unknown argument '{argument}' passed to '{func}'")
+            }
         }
     }
 }
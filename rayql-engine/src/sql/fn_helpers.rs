@@ -1,6 +1,10 @@
 use rayql::{
     schema::{ArgumentValue, Arguments, FunctionCallContext, Schema},
-    sql::error::{FunctionError, ToSQLError},
+    sql::{
+        error::{FunctionError, ToSQLError},
+        to_sql::quote_identifier,
+    },
+    types::DataType,
 };
 
 macro_rules! single_arg_fn {
@@ -10,6 +14,7 @@ macro_rules! single_arg_fn {
             arguments: &Arguments,
             $context: &FunctionCallContext,
         ) -> Result<String, ToSQLError> {
+            check_named_arguments(stringify!($name), arguments, &[])?;
             let $arg_name = get_single_argument(stringify!($name), arguments)?;
 
             $body
@@ -67,6 +72,114 @@ pub(crate) fn get_single_argument(
     }
 }
 
+pub(crate) fn check_no_arguments(func: &str, arguments: &Arguments) -> Result<(), ToSQLError> {
+    if arguments.list.is_empty() && arguments.named.is_empty() {
+        return Ok(());
+    }
+
+    Err(ToSQLError::FunctionError {
+        source: FunctionError::InvalidArgument(format!("{} does not take any arguments", func)),
+        line_number: arguments.line_number,
+        column: arguments.column,
+    })
+}
+
+pub(crate) fn check_named_arguments(
+    func: &str,
+    arguments: &Arguments,
+    allowed: &[&str],
+) -> Result<(), ToSQLError> {
+    if let Some(named_argument) = arguments
+        .named
+        .iter()
+        .find(|named_argument| !allowed.contains(&named_argument.name.as_str()))
+    {
+        return Err(ToSQLError::FunctionError {
+            source: FunctionError::UnknownNamedArgument(
+                func.to_string(),
+                named_argument.name.clone(),
+            ),
+            line_number: named_argument.argument.line_number,
+            column: named_argument.argument.column,
+        });
+    }
+
+    for (index, named_argument) in arguments.named.iter().enumerate() {
+        if arguments.named[..index]
+            .iter()
+            .any(|previous| previous.name.eq(&named_argument.name))
+        {
+            return Err(ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(format!(
+                    "'{}' is passed more than once to {}",
+                    named_argument.name, func
+                )),
+                line_number: named_argument.argument.line_number,
+                column: named_argument.argument.column,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn referential_actions_to_sql(
+    arguments: &Arguments,
+    context: &FunctionCallContext,
+) -> Result<String, ToSQLError> {
+    let mut sql = String::new();
+
+    for (name, clause) in [("on_delete", "ON DELETE"), ("on_update", "ON UPDATE")] {
+        let argument = match arguments.get_named(name) {
+            Some(argument) => argument,
+            None => continue,
+        };
+
+        let action = match &argument.value {
+            ArgumentValue::Identifier(action) => match action.as_str() {
+                "cascade" => Some("CASCADE"),
+                "restrict" => Some("RESTRICT"),
+                "set_null" => Some("SET NULL"),
+                "set_default" => Some("SET DEFAULT"),
+                "no_action" => Some("NO ACTION"),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let action = match action {
+            Some(action) => action,
+            None => {
+                return Err(ToSQLError::FunctionError {
+                    source: FunctionError::InvalidArgument(format!(
+                        "{} must be one of cascade, restrict, set_null, set_default or no_action, got {:?}",
+                        name, argument.value
+                    )),
+                    line_number: argument.line_number,
+                    column: argument.column,
+                })
+            }
+        };
+
+        if action.eq("SET NULL")
+            && !matches!(context.property_data_type.data_type, DataType::Optional(_))
+        {
+            return Err(ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(format!(
+                    "set_null cannot be used on non-optional field '{}'",
+                    context.property_name
+                )),
+                line_number: argument.line_number,
+                column: argument.column,
+            });
+        }
+
+        sql.push_str(&format!(" {} {}", clause, action));
+    }
+
+    Ok(sql)
+}
+
 pub(crate) fn check_value(
     schema: &Schema,
     context: &FunctionCallContext,
@@ -74,14 +187,17 @@ pub(crate) fn check_value(
     check_type: &str,
     operator: &str,
 ) -> Result<String, ToSQLError> {
+    check_named_arguments(check_type, arguments, &[])?;
     let argument = get_single_argument(check_type, arguments)?;
 
     let (value, name) = match argument.value {
         ArgumentValue::Value(value) => {
             let name = match context.property_data_type.data_type {
-                rayql::types::DataType::String => format!("LENGTH({})", context.property_name),
+                rayql::types::DataType::String => {
+                    format!("LENGTH({})", quote_identifier(&context.column_name))
+                }
                 rayql::types::DataType::Integer | rayql::types::DataType::Real => {
-                    context.property_name.clone()
+                    quote_identifier(&context.column_name)
                 }
                 _ => {
                     return Err(ToSQLError::FunctionError {
@@ -98,7 +214,7 @@ pub(crate) fn check_value(
             Ok((name, value.to_sql()))
         }
         ArgumentValue::FunctionCall(func) => {
-            Ok((context.property_name.clone(), func.to_sql(schema)?))
+            Ok((quote_identifier(&context.column_name), func.to_sql(schema)?))
         }
         _ => {
             return Err(ToSQLError::FunctionError {
@@ -1,39 +1,155 @@
 use rayql::{
-    schema::{ArgumentValue, Arguments, FunctionCallContext, Schema},
-    sql::error::{FunctionError, ToSQLError},
+    schema::{Argument, ArgumentValue, Arguments, FunctionCallContext, Schema, Validation},
+    sql::{
+        error::{FunctionError, ToSQLError},
+        to_sql::quote_identifier,
+    },
     types::DataType,
+    Value,
 };
 
-use rayql::sql::fn_helpers::{check_value, get_single_argument};
+use rayql::sql::fn_helpers::{
+    check_named_arguments, check_no_arguments, check_value, get_single_argument,
+    referential_actions_to_sql,
+};
 
 check_value_fn!(min, "<=");
 
 check_value_fn!(max, ">=");
 
-single_arg_fn!(foreign_key(schema, argument, context) {
+pub fn foreign_key(
+    schema: &Schema,
+    arguments: &Arguments,
+    context: &FunctionCallContext,
+) -> Result<String, ToSQLError> {
+    check_named_arguments("foreign_key", arguments, &["on_delete", "on_update"])?;
+    let argument = get_single_argument("foreign_key", arguments)?;
+
     let reference = argument_matches!(
         argument,
         ArgumentValue::Reference(reference) => reference.field_reference_to_sql(schema)?
     );
 
     Ok(format!(
-        "    FOREIGN KEY ({}) REFERENCES {}",
-        &context.property_name, reference
+        "    FOREIGN KEY ({}) REFERENCES {}{}",
+        quote_identifier(&context.column_name),
+        reference,
+        referential_actions_to_sql(arguments, context)?
     ))
-});
+}
+
+pub fn references(
+    schema: &Schema,
+    arguments: &Arguments,
+    context: &FunctionCallContext,
+) -> Result<String, ToSQLError> {
+    check_named_arguments("references", arguments, &["on_delete", "on_update"])?;
+    let argument = get_single_argument("references", arguments)?;
 
-single_arg_fn!(references(schema, argument, _context) {
     let reference = argument_matches!(
         argument,
         ArgumentValue::Reference(reference) => reference.field_reference_to_sql(schema)?
     );
 
-    Ok(format!("REFERENCES {}", reference))
-});
+    Ok(format!(
+        "REFERENCES {}{}",
+        reference,
+        referential_actions_to_sql(arguments, context)?
+    ))
+}
 
 single_arg_fn!(default(schema, argument, context) {
-    let value = argument_matches!(
+    Ok(format!("DEFAULT {}", column_value(schema, argument, context)?))
+});
+
+// A random version 4 uuid, SQLite has no builtin function to generate one.
+pub fn uuid() -> String {
+    "(lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || \
+     substr(lower(hex(randomblob(2))), 2) || '-' || \
+     substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || \
+     lower(hex(randomblob(6))))"
+        .to_string()
+}
+
+pub fn collate(
+    schema: &Schema,
+    arguments: &Arguments,
+    context: &FunctionCallContext,
+) -> Result<String, ToSQLError> {
+    check_named_arguments("collate", arguments, &[])?;
+    let argument = get_single_argument("collate", arguments)?;
+
+    let collation = match &argument.value {
+        ArgumentValue::Identifier(collation)
+            if ["nocase", "binary", "rtrim"].contains(&collation.as_str()) =>
+        {
+            collation.to_uppercase()
+        }
+        value => {
+            return Err(ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(format!(
+                    "collate must be one of nocase, binary or rtrim, got {:?}",
+                    value
+                )),
+                line_number: argument.line_number,
+                column: argument.column,
+            })
+        }
+    };
+
+    let data_type = schema.column_data_type(&context.property_data_type)?;
+    if !matches!(
+        data_type.without_optional(),
+        DataType::String | DataType::Date | DataType::Time | DataType::Uuid
+    ) {
+        return Err(ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(format!(
+                "collate can only be used on text fields, '{}' is {}",
+                context.property_name, context.property_data_type.data_type
+            )),
+            line_number: arguments.line_number,
+            column: arguments.column,
+        });
+    }
+
+    Ok(format!("COLLATE {}", collation))
+}
+
+single_arg_fn!(on_update(schema, argument, context) {
+    column_value(schema, argument, context)
+});
+
+// A value which can be written to the field, either a literal of the field's
+// type, a function call or a variant of the field's enum.
+pub fn column_value(
+    schema: &Schema,
+    argument: Argument,
+    context: &FunctionCallContext,
+) -> Result<String, ToSQLError> {
+    argument_matches!(
         argument,
+        ArgumentValue::Value(Value::StringLiteral(json)) if context.property_data_type.data_type.is_json() => {
+            rayql::sql::json::parse(&json)
+                .and_then(|value| {
+                    rayql::sql::json::check_shape(schema, &value, &context.property_data_type.data_type)
+                })
+                .map_err(|reason| ToSQLError::FunctionError {
+                    source: FunctionError::InvalidArgument(format!("invalid json: {}", reason)),
+                    line_number: argument.line_number,
+                    column: argument.column,
+                })?;
+
+            Ok(Value::StringLiteral(json).to_sql())
+        },
+        ArgumentValue::Value(value) if context.property_data_type.data_type.is_formatted_text() => {
+            let literal = context.property_data_type.data_type.format_literal(&value).map_err(|reason| ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(reason),
+                line_number: argument.line_number,
+                column: argument.column,
+            })?;
+
+            Ok(Value::StringLiteral(literal).to_sql())
+        },
         ArgumentValue::Value(value) if value.get_type().eq(&context.property_data_type.data_type) => {
             Ok(value.to_sql())
         },
@@ -47,7 +163,204 @@ single_arg_fn!(default(schema, argument, context) {
 
             reference.variant_reference_to_sql(schema)
         },
-    )?;
+    )
+}
 
-    Ok(format!("DEFAULT {}", value))
-});
+// Reads the validation rule of a validator function, `None` if the function
+// is not a validator.
+pub fn validation(
+    name: &str,
+    arguments: &Arguments,
+    context: &FunctionCallContext,
+) -> Result<Option<Validation>, ToSQLError> {
+    let validation = match name {
+        "pattern" | "like" => {
+            check_named_arguments(name, arguments, &[])?;
+            let argument = get_single_argument(name, arguments)?;
+
+            let pattern = argument_matches!(
+                argument,
+                ArgumentValue::Value(Value::StringLiteral(pattern)) => pattern
+            );
+
+            if name.eq("pattern") {
+                Validation::Pattern(pattern)
+            } else {
+                Validation::Like(pattern)
+            }
+        }
+        "length" => length(arguments)?,
+        "between" => return between(arguments, context).map(Some),
+        "email" => {
+            check_no_arguments(name, arguments)?;
+            Validation::Email
+        }
+        "url" => {
+            check_no_arguments(name, arguments)?;
+            Validation::Url
+        }
+        _ => return Ok(None),
+    };
+
+    let data_type = context.property_data_type.data_type.without_optional();
+
+    if data_type.ne(&DataType::String) {
+        return Err(ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(format!(
+                "{} can only be used on str fields, '{}' is {}",
+                name, context.property_name, data_type
+            )),
+            line_number: arguments.line_number,
+            column: arguments.column,
+        });
+    }
+
+    Ok(Some(validation))
+}
+
+fn length(arguments: &Arguments) -> Result<Validation, ToSQLError> {
+    check_named_arguments("length", arguments, &["min", "max"])?;
+
+    let (min, max) = match (arguments.list.as_slice(), arguments.named.is_empty()) {
+        ([min, max], true) => (Some(min), Some(max)),
+        ([], false) => (arguments.get_named("min"), arguments.get_named("max")),
+        _ => {
+            return Err(ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(
+                    "length expects length(min, max) or named min: and max: arguments".to_string(),
+                ),
+                line_number: arguments.line_number,
+                column: arguments.column,
+            })
+        }
+    };
+
+    let bound = |argument: Option<&Argument>| match argument {
+        None => Ok(None),
+        Some(Argument {
+            value: ArgumentValue::Value(Value::Integer(length)),
+            ..
+        }) if *length >= 0 => Ok(Some(*length)),
+        Some(argument) => Err(ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(format!(
+                "length bounds must be non-negative integers, got {:?}",
+                argument.value
+            )),
+            line_number: argument.line_number,
+            column: argument.column,
+        }),
+    };
+
+    let (min, max) = (bound(min)?, bound(max)?);
+
+    if matches!((min, max), (Some(min), Some(max)) if min > max) {
+        return Err(ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(
+                "the minimum length is greater than the maximum length".to_string(),
+            ),
+            line_number: arguments.line_number,
+            column: arguments.column,
+        });
+    }
+
+    Ok(Validation::Length { min, max })
+}
+
+fn between(arguments: &Arguments, context: &FunctionCallContext) -> Result<Validation, ToSQLError> {
+    check_named_arguments("between", arguments, &[])?;
+
+    let data_type = context.property_data_type.data_type.without_optional();
+
+    if !data_type.is_formatted_text()
+        && !matches!(
+            data_type,
+            DataType::Integer | DataType::Real | DataType::String | DataType::Timestamp
+        )
+    {
+        return Err(ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(format!(
+                "between cannot be used on {} fields",
+                data_type
+            )),
+            line_number: arguments.line_number,
+            column: arguments.column,
+        });
+    }
+
+    let (low, high) = match arguments.list.as_slice() {
+        [low, high] => (low, high),
+        _ => {
+            return Err(ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(
+                    "between expects a lower and an upper bound".to_string(),
+                ),
+                line_number: arguments.line_number,
+                column: arguments.column,
+            })
+        }
+    };
+
+    let bound = |argument: &Argument| {
+        let value = match &argument.value {
+            ArgumentValue::Value(value) if data_type.is_formatted_text() => data_type
+                .format_literal(value)
+                .map(Value::StringLiteral)
+                .ok(),
+            ArgumentValue::Value(value @ (Value::Integer(_) | Value::Real(_)))
+                if matches!(data_type, DataType::Integer | DataType::Real) =>
+            {
+                match (value, data_type) {
+                    (Value::Real(_), DataType::Integer) => None,
+                    _ => Some(value.clone()),
+                }
+            }
+            ArgumentValue::Value(value @ Value::StringLiteral(_))
+                if matches!(data_type, DataType::String | DataType::Timestamp) =>
+            {
+                Some(value.clone())
+            }
+            _ => None,
+        };
+
+        value.ok_or_else(|| ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(format!(
+                "between bounds must be {} literals, got {:?}",
+                data_type, argument.value
+            )),
+            line_number: argument.line_number,
+            column: argument.column,
+        })
+    };
+
+    let (low, high) = (bound(low)?, bound(high)?);
+
+    let is_reversed = match (&low, &high) {
+        (Value::StringLiteral(low), Value::StringLiteral(high))
+            if !matches!(data_type, DataType::Decimal { .. }) =>
+        {
+            low > high
+        }
+        (low, high) => as_number(low) > as_number(high),
+    };
+
+    if is_reversed {
+        return Err(ToSQLError::FunctionError {
+            source: FunctionError::InvalidArgument(
+                "the lower bound of between is greater than the upper bound".to_string(),
+            ),
+            line_number: arguments.line_number,
+            column: arguments.column,
+        });
+    }
+
+    Ok(Validation::Between(low, high))
+}
+
+fn as_number(value: &Value) -> f64 {
+    match value {
+        Value::Integer(integer) => *integer as f64,
+        Value::Real(real) => *real,
+        Value::StringLiteral(decimal) => decimal.parse().unwrap_or_default(),
+        Value::Boolean(boolean) => *boolean as i64 as f64,
+    }
+}
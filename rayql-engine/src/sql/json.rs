@@ -0,0 +1,304 @@
+use rayql::{schema::Schema, types::DataType, Value};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValue {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    fn kind(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Boolean(_) => "a boolean",
+            JsonValue::Number(_) => "a number",
+            JsonValue::String(_) => "a string",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        }
+    }
+}
+
+// Parses a JSON document, used to validate json literals at compile time.
+pub fn parse(input: &str) -> Result<JsonValue, String> {
+    let mut parser = JsonParser {
+        chars: input.chars().peekable(),
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+
+    match parser.chars.next() {
+        Some(ch) => Err(format!("unexpected character '{}' after the document", ch)),
+        None => Ok(value),
+    }
+}
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> JsonParser<'a> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(ch) if ch.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+
+        match self.chars.next() {
+            Some(ch) if ch == expected => Ok(()),
+            Some(ch) => Err(format!("expected '{}', got '{}'", expected, ch)),
+            None => Err(format!(
+                "expected '{}', got the end of the document",
+                expected
+            )),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+
+        match self.chars.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some(ch) if ch.is_ascii_digit() || *ch == '-' => self.parse_number(),
+            Some(ch) if ch.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while matches!(self.chars.peek(), Some(ch) if ch.is_ascii_alphabetic()) {
+                    word.push(self.chars.next().unwrap());
+                }
+
+                match word.as_str() {
+                    "null" => Ok(JsonValue::Null),
+                    "true" => Ok(JsonValue::Boolean(true)),
+                    "false" => Ok(JsonValue::Boolean(false)),
+                    _ => Err(format!("unexpected word '{}'", word)),
+                }
+            }
+            Some(ch) => Err(format!("unexpected character '{}'", ch)),
+            None => Err("unexpected end of the document".to_string()),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        self.expect('{')?;
+        let mut members: Vec<(String, JsonValue)> = vec![];
+
+        self.skip_whitespace();
+        if let Some('}') = self.chars.peek() {
+            self.chars.next();
+            return Ok(JsonValue::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.chars.peek().ne(&Some(&'"')) {
+                return Err("expected a string key".to_string());
+            }
+
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            let value = self.parse_value()?;
+
+            if members.iter().any(|(existing, _)| existing.eq(&key)) {
+                return Err(format!("duplicate key '{}'", key));
+            }
+            members.push((key, value));
+
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(JsonValue::Object(members)),
+                Some(ch) => return Err(format!("expected ',' or '}}', got '{}'", ch)),
+                None => return Err("unterminated object".to_string()),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, String> {
+        self.expect('[')?;
+        let mut items = vec![];
+
+        self.skip_whitespace();
+        if let Some(']') = self.chars.peek() {
+            self.chars.next();
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            items.push(self.parse_value()?);
+
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(JsonValue::Array(items)),
+                Some(ch) => return Err(format!("expected ',' or ']', got '{}'", ch)),
+                None => return Err("unterminated array".to_string()),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.chars.next() {
+                    Some(ch @ ('"' | '\\' | '/')) => string.push(ch),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                        match u32::from_str_radix(&hex, 16) {
+                            Ok(code) if hex.len() == 4 => {
+                                string.push(char::from_u32(code).unwrap_or('\u{fffd}'))
+                            }
+                            _ => return Err(format!("invalid unicode escape '\\u{}'", hex)),
+                        }
+                    }
+                    Some(ch) => return Err(format!("invalid escape '\\{}'", ch)),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some(ch) if ch.is_control() => {
+                    return Err("control characters must be escaped in strings".to_string())
+                }
+                Some(ch) => string.push(ch),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let mut number = String::new();
+        while matches!(self.chars.peek(), Some(ch) if ch.is_ascii_digit() || "+-.eE".contains(*ch))
+        {
+            number.push(self.chars.next().unwrap());
+        }
+
+        let digits = number.strip_prefix('-').unwrap_or(&number);
+        let leading_zero = digits.starts_with('0')
+            && digits.len() > 1
+            && !digits[1..].starts_with(['.', 'e', 'E']);
+        let missing_fraction =
+            digits.ends_with('.') || digits.contains(".e") || digits.contains(".E");
+        if leading_zero || missing_fraction {
+            return Err(format!("invalid number '{}'", number));
+        }
+
+        match number.parse::<f64>() {
+            Ok(value) if digits.starts_with(|ch: char| ch.is_ascii_digit()) => {
+                Ok(JsonValue::Number(value))
+            }
+            _ => Err(format!("invalid number '{}'", number)),
+        }
+    }
+}
+
+// Checks that a JSON value can be stored in a field of the given type, which
+// for `json<shape>` means matching the fields of the struct.
+pub fn check_shape(schema: &Schema, value: &JsonValue, data_type: &DataType) -> Result<(), String> {
+    let mismatch = || {
+        format!(
+            "expected {}, got {}",
+            data_type_kind(data_type),
+            value.kind()
+        )
+    };
+
+    match (data_type, value) {
+        (DataType::Optional(_), JsonValue::Null) | (DataType::Json(None), _) => Ok(()),
+        (DataType::Optional(inner), value) => check_shape(schema, value, inner),
+        (DataType::Json(Some(shape)), JsonValue::Object(members)) => {
+            let shape = match schema.get_struct(shape) {
+                Some(shape) => shape,
+                None => return Err(format!("struct '{}' is not declared", shape)),
+            };
+
+            if let Some((key, _)) = members
+                .iter()
+                .find(|(key, _)| shape.get_field(key).is_none())
+            {
+                return Err(format!("'{}' is not a field of '{}'", key, shape.name));
+            }
+
+            for field in &shape.fields {
+                match members.iter().find(|(key, _)| key.eq(&field.name)) {
+                    Some((_, member)) => check_shape(schema, member, &field.data_type.data_type)
+                        .map_err(|reason| format!("{}: {}", field.name, reason))?,
+                    None if matches!(field.data_type.data_type, DataType::Optional(_)) => {}
+                    None => return Err(format!("missing field '{}'", field.name)),
+                }
+            }
+
+            Ok(())
+        }
+        (DataType::List(inner), JsonValue::Array(items)) => items
+            .iter()
+            .try_for_each(|item| check_shape(schema, item, inner)),
+        (DataType::Enum(enum_name), JsonValue::String(_) | JsonValue::Number(_)) => {
+            let e = match schema.get_enum(enum_name) {
+                Some(e) => e,
+                None => return Err(format!("enum '{}' is not declared", enum_name)),
+            };
+
+            let is_variant = e
+                .variants
+                .iter()
+                .any(|variant| match (&variant.value, value) {
+                    (Value::StringLiteral(variant), JsonValue::String(s)) => variant.eq(s),
+                    (Value::Integer(variant), JsonValue::Number(n)) => (*variant as f64).eq(n),
+                    _ => false,
+                });
+
+            if is_variant {
+                Ok(())
+            } else {
+                Err(format!("not a variant of enum '{}'", enum_name))
+            }
+        }
+        (
+            DataType::Date | DataType::Time | DataType::Uuid | DataType::Decimal { .. },
+            JsonValue::String(s),
+        ) => data_type
+            .format_literal(&Value::StringLiteral(s.clone()))
+            .map(|_| ()),
+        (DataType::Decimal { .. }, JsonValue::Number(n)) => {
+            data_type.format_literal(&Value::Real(*n)).map(|_| ())
+        }
+        (DataType::String | DataType::Timestamp, JsonValue::String(_))
+        | (DataType::Real, JsonValue::Number(_))
+        | (DataType::Boolean, JsonValue::Boolean(_)) => Ok(()),
+        (DataType::Integer, JsonValue::Number(n)) if n.fract().eq(&0.0) => Ok(()),
+        _ => Err(mismatch()),
+    }
+}
+
+fn data_type_kind(data_type: &DataType) -> String {
+    match data_type {
+        DataType::String | DataType::Timestamp => "a string".to_string(),
+        DataType::Date => "a date".to_string(),
+        DataType::Time => "a time".to_string(),
+        DataType::Uuid => "a uuid".to_string(),
+        DataType::Decimal { .. } => "a decimal".to_string(),
+        DataType::Integer => "an integer".to_string(),
+        DataType::Real => "a number".to_string(),
+        DataType::Boolean => "a boolean".to_string(),
+        DataType::List(_) => "an array".to_string(),
+        DataType::Json(Some(shape)) => format!("an object of '{}'", shape),
+        DataType::Optional(inner) => format!("{} or null", data_type_kind(inner)),
+        data_type => data_type.to_string(),
+    }
+}
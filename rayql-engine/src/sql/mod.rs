@@ -6,3 +6,5 @@ pub mod error;
 mod fn_helpers;
 
 mod function;
+
+pub mod json;
@@ -1,7 +1,9 @@
 use rayql::{
     schema::{
-        Argument, ArgumentValue, Arguments, Enum, EnumVariant, FunctionCall, Model, Property,
-        Reference, Schema,
+        Argument, ArgumentValue, Arguments, BinaryOperator, ConstraintKind, DataTypeWithSpan, Enum,
+        EnumVariant, Expression, ExpressionKind, Field, FieldName, FunctionCall,
+        FunctionCallContext, Index, JoinKind, Model, ModelConstraint, ModelOptionKind, Property,
+        Reference, Schema, Search, Seed, UnaryOperator, Validation, View, SOFT_DELETE_FIELD,
     },
     sql::error::{FunctionError, ToSQLError},
     types::DataType,
@@ -12,76 +14,1350 @@ impl Schema {
     pub fn to_sql(&self) -> Result<Vec<String>, ToSQLError> {
         let mut sql_statements = Vec::new();
 
-        for model in &self.models {
-            let mut fields_sql = Vec::new();
-            let mut fk_sql = Vec::new();
+        let join_tables = self.join_tables()?;
 
-            for field in &model.fields {
-                let mut field_sql = format!(
-                    "    {} {}",
-                    field.name,
-                    field.data_type.data_type.to_sql(true)
-                );
+        let tables = self.models.iter().filter(|model| !model.is_abstract);
 
-                if let DataType::Enum(enum_name) = &field.data_type.data_type {
-                    let variants: Vec<String> = match self.get_enum(enum_name) {
-                        Some(e) => e
-                            .variants
-                            .iter()
-                            .map(|variant| format!("'{}'", variant.to_sql()))
-                            .collect(),
-                        None => {
-                            return Err(ToSQLError::EnumNotFound {
-                                enum_name: enum_name.clone(),
-                                line_number: field.data_type.line_number,
-                                column: field.data_type.column,
-                            })
-                        }
-                    };
-                    field_sql.push_str(&format!(
-                        " CHECK({} IN ({}))",
-                        field.name,
-                        variants.join(", ")
+        for model in tables.clone().chain(join_tables.iter()) {
+            sql_statements.push(
+                model
+                    .to_sql(self)
+                    .map_err(|error| error.in_file(&model.file))?,
+            );
+        }
+
+        for model in tables {
+            if let Some(trigger) = model
+                .on_update_trigger_to_sql(self)
+                .map_err(|error| error.in_file(&model.file))?
+            {
+                sql_statements.push(trigger);
+            }
+        }
+
+        let soft_delete_indexes: Vec<Index> = self
+            .models
+            .iter()
+            .filter(|model| !model.is_abstract)
+            .flat_map(|model| model.soft_delete_indexes(self))
+            .collect();
+
+        let indexes: Vec<&Index> = self.indexes.iter().chain(&soft_delete_indexes).collect();
+        self.check_index_names(&indexes, &join_tables)?;
+        self.check_search_names(&indexes, &join_tables)?;
+
+        for index in indexes {
+            sql_statements.push(
+                index
+                    .to_sql(self)
+                    .map_err(|error| error.in_file(&index.file))?,
+            );
+        }
+
+        for view in &self.views {
+            sql_statements.push(
+                view.to_sql(self)
+                    .map_err(|error| error.in_file(&view.file))?,
+            );
+        }
+
+        for model in self.models.iter().filter(|model| !model.is_abstract) {
+            if let Some(view) = model.active_view_to_sql() {
+                sql_statements.push(view);
+            }
+        }
+
+        for search in &self.searches {
+            sql_statements.extend(
+                search
+                    .to_sql(self)
+                    .map_err(|error| error.in_file(&search.file))?,
+            );
+        }
+
+        for seed in &self.seeds {
+            sql_statements.extend(
+                seed.to_sql(self)
+                    .map_err(|error| error.in_file(&seed.file))?,
+            );
+        }
+
+        Ok(sql_statements)
+    }
+}
+
+impl Schema {
+    // Whether a foreign key of any model references the field, SQLite requires
+    // the parent key to be a primary key or to have a plain unique constraint.
+    fn is_foreign_key_target(&self, model: &Model, field: &Field) -> bool {
+        self.models
+            .iter()
+            .flat_map(|model| &model.fields)
+            .flat_map(|field| &field.properties)
+            .filter_map(|property| match property {
+                Property::FunctionCall(FunctionCall {
+                    name, arguments, ..
+                }) if name.eq("foreign_key") || name.eq("references") => arguments.get_first(),
+                _ => None,
+            })
+            .any(|argument| {
+                matches!(&argument.value, ArgumentValue::Reference(reference)
+                    if reference.entity.eq(&model.name) && reference.property.eq(&field.name))
+            })
+    }
+
+    // Indexes share their namespace with tables and views, a name used twice
+    // would make `IF NOT EXISTS` silently skip the second declaration.
+    fn check_index_names(
+        &self,
+        indexes: &[&Index],
+        join_tables: &[Model],
+    ) -> Result<(), ToSQLError> {
+        for (position, index) in indexes.iter().enumerate() {
+            let name = index.get_name();
+            let is_named = |other: &str| other.eq_ignore_ascii_case(&name);
+
+            let same_name = indexes[..position]
+                .iter()
+                .find(|other| is_named(&other.get_name()));
+
+            let reason = if let Some(other) = same_name {
+                // derived names do not include the condition of partial indexes
+                if index.condition.is_some() || other.condition.is_some() {
+                    "another index has the same name, name partial indexes explicitly"
+                } else {
+                    "another index has the same name"
+                }
+            } else if self
+                .models
+                .iter()
+                .chain(join_tables)
+                .any(|model| is_named(model.table_name()))
+            {
+                "a table has the same name"
+            } else if self.views.iter().any(|view| is_named(&view.name)) {
+                "a view has the same name"
+            } else {
+                continue;
+            };
+
+            return Err(ToSQLError::InvalidIndex {
+                index_name: name.clone(),
+                reason: reason.to_string(),
+                line_number: index.line_number,
+                column: index.column,
+            }
+            .in_file(&index.file));
+        }
+
+        Ok(())
+    }
+
+    // Search tables share the same namespace, a model whose `<table>_search`
+    // name is taken would keep searching the other table.
+    fn check_search_names(
+        &self,
+        indexes: &[&Index],
+        join_tables: &[Model],
+    ) -> Result<(), ToSQLError> {
+        for search in &self.searches {
+            let name = match self.get_model(&search.model) {
+                Some(model) => search_table_name(model),
+                None => continue,
+            };
+            let is_named = |other: &str| other.eq_ignore_ascii_case(&name);
+
+            let used_by = if self
+                .models
+                .iter()
+                .chain(join_tables)
+                .any(|model| is_named(model.table_name()))
+            {
+                "a table"
+            } else if self.views.iter().any(|view| is_named(&view.name)) {
+                "a view"
+            } else if indexes.iter().any(|index| is_named(&index.get_name())) {
+                "an index"
+            } else {
+                continue;
+            };
+
+            return Err(search
+                .invalid(format!(
+                    "the search table '{}' has the same name as {}",
+                    name, used_by
+                ))
+                .in_file(&search.file));
+        }
+
+        Ok(())
+    }
+
+    // The table of a model, used where the model is known to exist.
+    fn table_name<'a>(&'a self, model_name: &'a str) -> &'a str {
+        self.get_model(model_name)
+            .map_or(model_name, |model| model.table_name())
+    }
+
+    // The type of the column storing a field, enums are stored as the type of
+    // their values.
+    pub fn column_data_type(&self, data_type: &DataTypeWithSpan) -> Result<DataType, ToSQLError> {
+        let enum_name = match data_type.data_type.get_enum_name() {
+            Some(enum_name) => enum_name,
+            None => return Ok(data_type.data_type.clone()),
+        };
+
+        let value_type = match self.get_enum(enum_name) {
+            Some(e) => e.data_type.clone(),
+            None => {
+                return Err(ToSQLError::EnumNotFound {
+                    enum_name: enum_name.to_string(),
+                    line_number: data_type.line_number,
+                    column: data_type.column,
+                })
+            }
+        };
+
+        match data_type.data_type {
+            DataType::Optional(_) => Ok(DataType::Optional(Box::new(value_type))),
+            _ => Ok(value_type),
+        }
+    }
+}
+
+impl Model {
+    pub fn to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
+        let mut fields_sql = Vec::new();
+        let mut fk_sql = Vec::new();
+        let strict = self.get_option(&ModelOptionKind::Strict).is_some();
+
+        self.check_column_names()?;
+
+        for field in self.fields.iter().filter(|field| !field.is_relation()) {
+            fields_sql.push(
+                self.column_to_sql(field, schema, strict, &mut fk_sql)
+                    .map_err(|error| error.in_file(&field.file))?,
+            );
+        }
+
+        self.check_primary_keys()?;
+        self.check_without_rowid()?;
+        self.check_soft_delete()?;
+
+        for constraint in &self.constraints {
+            if self.is_soft_delete() && constraint.kind.eq(&ConstraintKind::Unique) {
+                continue;
+            }
+            fields_sql.push(
+                constraint
+                    .to_sql(self, schema)
+                    .map_err(|error| error.in_file(&constraint.file))?,
+            );
+        }
+
+        fields_sql.extend(fk_sql);
+
+        let options: Vec<&str> = self
+            .options
+            .iter()
+            .filter_map(|option| match option.kind {
+                ModelOptionKind::Strict => Some("STRICT"),
+                ModelOptionKind::WithoutRowid => Some("WITHOUT ROWID"),
+                ModelOptionKind::SoftDelete => None,
+            })
+            .collect();
+
+        Ok(format!(
+            "{}CREATE TABLE IF NOT EXISTS {} (\n{}\n){};",
+            doc_comment_to_sql(&self.doc, ""),
+            quote_identifier(self.table_name()),
+            fields_sql.join(",\n"),
+            if options.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", options.join(", "))
+            }
+        ))
+    }
+}
+
+impl Model {
+    // SQLite has no `ON UPDATE` column clause, fields using `on_update(...)`
+    // are refreshed by a single `AFTER UPDATE` trigger per model. The trigger
+    // is skipped when one of those fields is set explicitly.
+    fn column_to_sql(
+        &self,
+        field: &Field,
+        schema: &Schema,
+        strict: bool,
+        fk_sql: &mut Vec<String>,
+    ) -> Result<String, ToSQLError> {
+        let column_name = &quote_identifier(field.column_name());
+        let mut field_sql = format!(
+            "{}    {} {}",
+            doc_comment_to_sql(&field.doc, "    "),
+            column_name,
+            schema
+                .column_data_type(&field.data_type)?
+                .to_sql(true, strict)
+        );
+
+        if let DataType::Enum(enum_name) = &field.data_type.data_type {
+            let variants: Vec<String> = match schema.get_enum(enum_name) {
+                Some(e) => e.variants.iter().map(|variant| variant.to_sql()).collect(),
+                None => {
+                    return Err(ToSQLError::EnumNotFound {
+                        enum_name: enum_name.clone(),
+                        line_number: field.data_type.line_number,
+                        column: field.data_type.column,
+                    })
+                }
+            };
+            field_sql.push_str(&format!(
+                " CHECK({} IN ({}))",
+                column_name,
+                variants.join(", ")
+            ));
+        }
+
+        if field.data_type.data_type.is_json() {
+            field_sql.push_str(&match field.data_type.data_type.get_json_shape() {
+                Some(shape) if schema.get_struct(shape).is_none() => {
+                    return Err(ToSQLError::StructNotFound {
+                        struct_name: shape.to_string(),
+                        line_number: field.data_type.line_number,
+                        column: field.data_type.column,
+                    })
+                }
+                Some(_) => format!(
+                    " CHECK(json_valid({0}) AND json_type({0}) = 'object')",
+                    column_name
+                ),
+                None => format!(" CHECK(json_valid({}))", column_name),
+            });
+        }
+
+        if let Some(check) = field.data_type.data_type.format_check_to_sql(column_name) {
+            field_sql.push_str(&format!(" CHECK({})", check));
+        }
+
+        for prop in &field.properties {
+            match prop {
+                Property::FunctionCall(FunctionCall {
+                    name,
+                    context,
+                    arguments,
+                    ..
+                }) if name.eq("foreign_key") => {
+                    fk_sql.push(rayql::sql::function::foreign_key(
+                        schema, arguments, context,
+                    )?);
+                }
+                // applied by the model's update trigger
+                Property::FunctionCall(FunctionCall { name, .. }) if name.eq("on_update") => {}
+                // replaced by a unique index ignoring deleted rows, unless a
+                // foreign key references the column
+                Property::Unique
+                    if self.is_soft_delete() && !schema.is_foreign_key_target(self, field) => {}
+                Property::Check(expression) => {
+                    self.check_condition(expression, schema)?;
+                    field_sql.push_str(&format!(" {}", prop.to_sql(self, schema)?));
+                }
+                Property::Generated { expression, .. } => {
+                    self.check_generated_column(field, expression, schema)?;
+                    field_sql.push_str(&format!(" {}", prop.to_sql(self, schema)?));
+                }
+                _ => field_sql.push_str(&format!(" {}", prop.to_sql(self, schema)?)),
+            }
+        }
+
+        Ok(field_sql)
+    }
+
+    pub fn on_update_trigger_to_sql(&self, schema: &Schema) -> Result<Option<String>, ToSQLError> {
+        let mut assignments = Vec::new();
+        let mut conditions = Vec::new();
+
+        for field in self.fields.iter().filter(|field| !field.is_relation()) {
+            let func = match field.get_function_call("on_update") {
+                Some(func) => func,
+                None => continue,
+            };
+
+            if field.is_read_only() {
+                return Err(ToSQLError::InvalidProperty {
+                    model_name: self.name.clone(),
+                    field_name: field.name.clone(),
+                    reason: "a generated column cannot have on_update".to_string(),
+                    line_number: func.line_number,
+                    column: func.column,
+                }
+                .in_file(&field.file));
+            }
+
+            let column_name = quote_identifier(field.column_name());
+            let value = func
+                .to_sql(schema)
+                .map_err(|error| error.in_file(&field.file))?;
+            assignments.push(format!("{} = {}", column_name, value));
+            conditions.push(format!("NEW.{0} IS OLD.{0}", column_name));
+        }
+
+        if assignments.is_empty() {
+            return Ok(None);
+        }
+
+        let key: Vec<String> = self
+            .row_key()
+            .iter()
+            .map(|column| format!("{0} = NEW.{0}", quote_identifier(column)))
+            .collect();
+
+        Ok(Some(format!(
+            "CREATE TRIGGER IF NOT EXISTS {0}\nAFTER UPDATE ON {1}\nFOR EACH ROW\nWHEN {2}\nBEGIN\n    UPDATE {1} SET {3} WHERE {4};\nEND;",
+            quote_identifier(&format!("{}_on_update", self.table_name())),
+            quote_identifier(self.table_name()),
+            conditions.join(" AND "),
+            assignments.join(", "),
+            key.join(" AND ")
+        )))
+    }
+
+    // Columns identifying a row, the primary key or SQLite's implicit rowid.
+    fn row_key(&self) -> Vec<String> {
+        let primary_key: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|field| field.properties.contains(&Property::PrimaryKey))
+            .map(|field| field.column_name().to_string())
+            .chain(
+                self.constraints
+                    .iter()
+                    .filter(|constraint| constraint.kind.eq(&ConstraintKind::PrimaryKey))
+                    .flat_map(|constraint| {
+                        constraint.fields.iter().map(|field_name| {
+                            self.get_field(&field_name.name)
+                                .map_or(field_name.name.clone(), |field| {
+                                    field.column_name().to_string()
+                                })
+                        })
+                    }),
+            )
+            .collect();
+
+        if primary_key.is_empty() {
+            vec!["rowid".to_string()]
+        } else {
+            primary_key
+        }
+    }
+}
+
+impl Index {
+    pub fn to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
+        let model = match schema.get_model(&self.model) {
+            Some(model) => model,
+            None => {
+                return Err(ToSQLError::ModelNotFound {
+                    model_name: self.model.clone(),
+                    line_number: self.line_number,
+                    column: self.column,
+                })
+            }
+        };
+
+        if model.is_abstract {
+            return Err(model.abstract_model(self.line_number, self.column));
+        }
+
+        let mut columns = model.field_names_to_sql(&self.columns)?;
+        let unique = if self.unique { "UNIQUE " } else { "" };
+
+        // the index compares values the same way as its columns
+        for (column, field_name) in columns.iter_mut().zip(&self.columns) {
+            let collate = model
+                .get_field(&field_name.name)
+                .and_then(|field| field.get_function_call("collate"));
+
+            if let Some(collate) = collate {
+                column.push_str(&format!(" {}", collate.to_sql(schema)?));
+            }
+        }
+
+        let mut conditions = Vec::new();
+
+        if let Some(condition) = &self.condition {
+            model.check_condition(condition, schema)?;
+            conditions.push(condition.operand_to_sql(
+                BinaryOperator::And.precedence(),
+                model,
+                schema,
+            )?);
+        }
+
+        // deleted rows do not take part in unique indexes
+        if let Some(condition) = model.active_condition().filter(|_| self.unique) {
+            conditions.push(condition);
+        }
+
+        let condition = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        Ok(format!(
+            "CREATE {}INDEX IF NOT EXISTS {} ON {} ({}){};",
+            unique,
+            quote_identifier(&self.get_name()),
+            quote_identifier(model.table_name()),
+            columns.join(", "),
+            condition
+        ))
+    }
+}
+
+impl View {
+    pub fn to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
+        let base = match self.columns.first() {
+            Some(column) => &column.reference.entity,
+            None => return Err(self.invalid("a view must select at least one column".to_string())),
+        };
+
+        let mut models = vec![base.as_str()];
+        let mut joins_sql = Vec::new();
+
+        for join in &self.joins {
+            let left = join.left.column_reference_to_sql(schema)?;
+            let right = join.right.column_reference_to_sql(schema)?;
+
+            let joined = match (
+                models.contains(&join.left.entity.as_str()),
+                models.contains(&join.right.entity.as_str()),
+            ) {
+                (true, false) => &join.right.entity,
+                (false, true) => &join.left.entity,
+                _ => {
+                    return Err(ToSQLError::InvalidView {
+                        view_name: self.name.clone(),
+                        reason: format!(
+                            "join must connect a model already in the view to a new one, got '{}' and '{}'",
+                            join.left.entity, join.right.entity
+                        ),
+                        line_number: join.line_number,
+                        column: join.column,
+                    })
+                }
+            };
+
+            models.push(joined);
+            joins_sql.push(format!(
+                "{} {} ON {} = {}",
+                join.kind.to_sql(),
+                quote_identifier(schema.table_name(joined)),
+                left,
+                right
+            ));
+        }
+
+        let mut columns_sql = Vec::new();
+
+        for column in &self.columns {
+            if !models.contains(&column.reference.entity.as_str()) {
+                return Err(ToSQLError::InvalidView {
+                    view_name: self.name.clone(),
+                    reason: format!(
+                        "model '{}' is not joined in the view",
+                        column.reference.entity
+                    ),
+                    line_number: column.line_number,
+                    column: column.column,
+                });
+            }
+
+            let column_sql = column.reference.column_reference_to_sql(schema)?;
+
+            // mapped columns keep the name of their field in the view
+            let is_mapped = schema
+                .get_model(&column.reference.entity)
+                .and_then(|model| model.get_field(&column.reference.property))
+                .is_some_and(|field| field.map.is_some());
+
+            columns_sql.push(if column.alias.is_some() || is_mapped {
+                format!("{} AS {}", column_sql, quote_identifier(column.get_name()))
+            } else {
+                column_sql
+            });
+        }
+
+        let mut select_sql = format!(
+            "SELECT {}\nFROM {}",
+            columns_sql.join(", "),
+            quote_identifier(schema.table_name(base))
+        );
+        for join_sql in joins_sql {
+            select_sql.push_str(&format!("\n{}", join_sql));
+        }
+
+        Ok(format!(
+            "{}CREATE VIEW IF NOT EXISTS {} AS\n{};",
+            doc_comment_to_sql(&self.doc, ""),
+            quote_identifier(&self.name),
+            select_sql
+        ))
+    }
+
+    fn invalid(&self, reason: String) -> ToSQLError {
+        ToSQLError::InvalidView {
+            view_name: self.name.clone(),
+            reason,
+            line_number: self.line_number,
+            column: self.column,
+        }
+    }
+}
+
+impl Search {
+    pub fn to_sql(&self, schema: &Schema) -> Result<Vec<String>, ToSQLError> {
+        let model = match schema.get_model(&self.model) {
+            Some(model) if model.is_abstract => {
+                return Err(model.abstract_model(self.line_number, self.column))
+            }
+            Some(model) => model,
+            None => {
+                return Err(ToSQLError::ModelNotFound {
+                    model_name: self.model.clone(),
+                    line_number: self.line_number,
+                    column: self.column,
+                })
+            }
+        };
+
+        if schema
+            .searches
+            .iter()
+            .filter(|search| search.model.eq(&self.model))
+            .count()
+            > 1
+        {
+            return Err(self.invalid("a model can only have one search table".to_string()));
+        }
+
+        if model.get_option(&ModelOptionKind::WithoutRowid).is_some() {
+            return Err(self.invalid(
+                "search tables need the rowid of the model, which is declared without_rowid"
+                    .to_string(),
+            ));
+        }
+
+        let mut columns = Vec::new();
+
+        for (position, field_name) in self.fields.iter().enumerate() {
+            if self.fields[..position]
+                .iter()
+                .any(|other| other.name.eq(&field_name.name))
+            {
+                return Err(ToSQLError::InvalidSearch {
+                    model_name: self.model.clone(),
+                    reason: format!("'{}' is listed more than once", field_name.name),
+                    line_number: field_name.line_number,
+                    column: field_name.column,
+                });
+            }
+
+            let field = match model.get_field(&field_name.name) {
+                Some(field) if !field.is_relation() => field,
+                _ => {
+                    return Err(ToSQLError::FieldNotFound {
+                        model_name: model.name.clone(),
+                        field_name: field_name.name.clone(),
+                        line_number: field_name.line_number,
+                        column: field_name.column,
+                    })
+                }
+            };
+
+            let data_type = schema.column_data_type(&field.data_type)?;
+            if !matches!(data_type.without_optional(), DataType::String) {
+                return Err(ToSQLError::InvalidSearch {
+                    model_name: self.model.clone(),
+                    reason: format!(
+                        "only text fields can be searched, '{}' is {}",
+                        field.name, field.data_type.data_type
+                    ),
+                    line_number: field_name.line_number,
+                    column: field_name.column,
+                });
+            }
+
+            columns.push(quote_identifier(field.column_name()));
+        }
+
+        // an integer primary key is an alias of the rowid
+        let rowid = match model.get_primary_key_field() {
+            Some(field) if field.data_type.data_type.eq(&DataType::Integer) => field.column_name(),
+            _ => "rowid",
+        };
+
+        let source = model.table_name();
+        let search_name = search_table_name(model);
+        let search = quote_identifier(&search_name);
+        let columns = columns.join(", ");
+        let values = |row: &str| {
+            std::iter::once(rowid)
+                .chain(self.fields.iter().map(|field_name| {
+                    model
+                        .get_field(&field_name.name)
+                        .map_or(field_name.name.as_str(), |field| field.column_name())
+                }))
+                .map(|column| format!("{}.{}", row, quote_identifier(column)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let insert = format!(
+            "INSERT INTO {0}(rowid, {1}) VALUES ({2});",
+            search,
+            columns,
+            values("NEW")
+        );
+        let delete = format!(
+            "INSERT INTO {0}({0}, rowid, {1}) VALUES ('delete', {2});",
+            search,
+            columns,
+            values("OLD")
+        );
+        let trigger = |event: &str, statements: Vec<&String>| {
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {}\nAFTER {} ON {}\nFOR EACH ROW\nBEGIN\n{}\nEND;",
+                quote_identifier(&format!("{}_{}", search_name, event.to_lowercase())),
+                event,
+                quote_identifier(source),
+                statements
+                    .iter()
+                    .map(|statement| format!("    {}", statement))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        };
+
+        Ok(vec![
+            format!(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING fts5({}, content={}, content_rowid={});",
+                search,
+                columns,
+                quote_string(source),
+                quote_string(rowid)
+            ),
+            trigger("INSERT", vec![&insert]),
+            trigger("DELETE", vec![&delete]),
+            trigger("UPDATE", vec![&delete, &insert]),
+        ])
+    }
+
+    fn invalid(&self, reason: String) -> ToSQLError {
+        ToSQLError::InvalidSearch {
+            model_name: self.model.clone(),
+            reason,
+            line_number: self.line_number,
+            column: self.column,
+        }
+    }
+}
+
+fn search_table_name(model: &Model) -> String {
+    format!("{}_search", model.table_name())
+}
+
+impl Seed {
+    pub fn to_sql(&self, schema: &Schema) -> Result<Vec<String>, ToSQLError> {
+        let model = match schema.get_model(&self.model) {
+            Some(model) if model.is_abstract => {
+                return Err(model.abstract_model(self.line_number, self.column))
+            }
+            Some(model) => model,
+            None => {
+                return Err(ToSQLError::ModelNotFound {
+                    model_name: self.model.clone(),
+                    line_number: self.line_number,
+                    column: self.column,
+                })
+            }
+        };
+
+        let key = match &self.upsert {
+            Some(key) => {
+                let field = model
+                    .get_field(&key.name)
+                    .filter(|field| !field.is_relation());
+                match field {
+                    Some(field) if model.is_unique_field(field, schema) => Some(field),
+                    Some(_) => {
+                        return Err(self.invalid(
+                            format!(
+                                "upsert key '{}' must be a primary key or unique field",
+                                key.name
+                            ),
+                            key.line_number,
+                            key.column,
+                        ))
+                    }
+                    None => {
+                        return Err(ToSQLError::FieldNotFound {
+                            model_name: model.name.clone(),
+                            field_name: key.name.clone(),
+                            line_number: key.line_number,
+                            column: key.column,
+                        })
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let mut statements = Vec::new();
+
+        for row in &self.rows {
+            if let Some(argument) = row.values.list.first() {
+                return Err(self.invalid(
+                    "row values must be written as `field: value`".to_string(),
+                    argument.line_number,
+                    argument.column,
+                ));
+            }
+
+            let mut columns = Vec::new();
+            let mut values = Vec::new();
+
+            for (position, named) in row.values.named.iter().enumerate() {
+                let argument = &named.argument;
+                if row.values.named[..position]
+                    .iter()
+                    .any(|other| other.name.eq(&named.name))
+                {
+                    return Err(self.invalid(
+                        format!("'{}' is set more than once", named.name),
+                        argument.line_number,
+                        argument.column,
                     ));
                 }
 
-                for prop in &field.properties {
-                    match prop {
-                        Property::FunctionCall(FunctionCall {
-                            name,
-                            context,
-                            arguments,
-                            ..
-                        }) if name.eq("foreign_key") => {
-                            fk_sql
-                                .push(rayql::sql::function::foreign_key(self, arguments, context)?);
-                        }
-                        _ => field_sql.push_str(&format!(" {}", prop.to_sql(self)?)),
+                let field = match model.get_field(&named.name) {
+                    Some(field) if !field.is_relation() => field,
+                    _ => {
+                        return Err(ToSQLError::FieldNotFound {
+                            model_name: model.name.clone(),
+                            field_name: named.name.clone(),
+                            line_number: argument.line_number,
+                            column: argument.column,
+                        })
                     }
+                };
+
+                if field.is_read_only() {
+                    return Err(self.invalid(
+                        format!("'{}' is a generated column and cannot be set", field.name),
+                        argument.line_number,
+                        argument.column,
+                    ));
                 }
 
-                fields_sql.push(field_sql);
+                columns.push(quote_identifier(field.column_name()));
+                values.push(self.value_to_sql(schema, field, argument)?);
             }
-            fields_sql.extend(fk_sql);
-            let model_sql = format!(
-                "CREATE TABLE IF NOT EXISTS {} (\n{}\n);",
-                model.name,
-                fields_sql.join(",\n")
-            );
-            sql_statements.push(model_sql);
+
+            let is_set = |field: &Field| {
+                row.values
+                    .named
+                    .iter()
+                    .any(|named| named.name.eq(&field.name))
+            };
+
+            if let Some(field) = model
+                .fields
+                .iter()
+                .find(|field| model.is_required_field(field) && !is_set(field))
+            {
+                return Err(self.invalid(
+                    format!("missing value for the required field '{}'", field.name),
+                    row.line_number,
+                    row.column,
+                ));
+            }
+
+            // without a key `INSERT OR IGNORE` would insert the row on every run
+            if key.is_none()
+                && !model
+                    .fields
+                    .iter()
+                    .any(|field| is_set(field) && model.is_unique_field(field, schema))
+            {
+                return Err(self.invalid(
+                    "the row sets no primary key or unique field, set one or use upsert(<field>)"
+                        .to_string(),
+                    row.line_number,
+                    row.column,
+                ));
+            }
+
+            let table = quote_identifier(model.table_name());
+            let values_sql = if columns.is_empty() {
+                "DEFAULT VALUES".to_string()
+            } else {
+                format!("({}) VALUES ({})", columns.join(", "), values.join(", "))
+            };
+
+            statements.push(match key {
+                Some(key) if !is_set(key) => {
+                    return Err(self.invalid(
+                        format!("missing value for the upsert key '{}'", key.name),
+                        row.line_number,
+                        row.column,
+                    ))
+                }
+                Some(key) => {
+                    let key_column = quote_identifier(key.column_name());
+                    // the conflict target has to match the partial unique index
+                    let target = match model.active_condition() {
+                        Some(condition) if !key.properties.contains(&Property::PrimaryKey) => {
+                            format!("({}) WHERE {}", key_column, condition)
+                        }
+                        _ => format!("({})", key_column),
+                    };
+                    let updates: Vec<String> = columns
+                        .iter()
+                        .filter(|column| column.ne(&&key_column))
+                        .map(|column| format!("{0} = excluded.{0}", column))
+                        .collect();
+
+                    format!(
+                        "INSERT INTO {} {}\nON CONFLICT {} {};",
+                        table,
+                        values_sql,
+                        target,
+                        if updates.is_empty() {
+                            "DO NOTHING".to_string()
+                        } else {
+                            format!("DO UPDATE SET {}", updates.join(", "))
+                        }
+                    )
+                }
+                None => format!("INSERT OR IGNORE INTO {} {};", table, values_sql),
+            });
         }
 
-        Ok(sql_statements)
+        Ok(statements)
+    }
+
+    fn value_to_sql(
+        &self,
+        schema: &Schema,
+        field: &Field,
+        argument: &Argument,
+    ) -> Result<String, ToSQLError> {
+        let data_type = &field.data_type.data_type;
+
+        match &argument.value {
+            ArgumentValue::Identifier(identifier) if identifier.eq("null") => {
+                return match data_type {
+                    DataType::Optional(_) => Ok("NULL".to_string()),
+                    _ => Err(self.invalid(
+                        format!("'{}' is required and cannot be null", field.name),
+                        argument.line_number,
+                        argument.column,
+                    )),
+                }
+            }
+            ArgumentValue::Value(value)
+                if !data_type.is_json()
+                    && !data_type.is_formatted_text()
+                    && value.get_type().ne(data_type.without_optional()) =>
+            {
+                return Err(self.invalid(
+                    format!(
+                        "'{}' expects {}, got {}",
+                        field.name,
+                        data_type.without_optional(),
+                        value
+                    ),
+                    argument.line_number,
+                    argument.column,
+                ))
+            }
+            ArgumentValue::Reference(reference) if data_type.get_enum_name().is_none() => {
+                return Err(self.invalid(
+                    format!(
+                        "'{}' is not an enum field, got '{}.{}'",
+                        field.name, reference.entity, reference.property
+                    ),
+                    argument.line_number,
+                    argument.column,
+                ))
+            }
+            _ => {}
+        }
+
+        // values are checked against the type of the field when present
+        let mut context = FunctionCallContext::new(
+            field.name.clone(),
+            DataTypeWithSpan {
+                data_type: data_type.without_optional().clone(),
+                ..field.data_type.clone()
+            },
+        );
+        context.column_name = field.column_name().to_string();
+
+        let mut argument = argument.clone();
+        if let ArgumentValue::FunctionCall(func) = &mut argument.value {
+            func.context = context.clone();
+        }
+
+        rayql::sql::function::column_value(schema, argument, &context)
+    }
+
+    fn invalid(&self, reason: String, line_number: usize, column: usize) -> ToSQLError {
+        ToSQLError::InvalidSeed {
+            model_name: self.model.clone(),
+            reason,
+            line_number,
+            column,
+        }
+    }
+}
+
+impl Model {
+    // Fields which have no value unless one is given when inserting a row.
+    fn is_required_field(&self, field: &Field) -> bool {
+        let is_rowid = field.properties.contains(&Property::PrimaryKey)
+            && field.data_type.data_type.eq(&DataType::Integer)
+            && self.get_option(&ModelOptionKind::WithoutRowid).is_none();
+
+        !field.is_relation()
+            && !field.is_read_only()
+            && !is_rowid
+            && !matches!(field.data_type.data_type, DataType::Optional(_))
+            && !field.has_function_call("default")
+            && !field.properties.contains(&Property::AutoIncrement)
+    }
+
+    // Unique constraints of soft delete models, as unique indexes which ignore
+    // deleted rows. Fields referenced by a foreign key keep their unique column.
+    pub fn soft_delete_indexes(&self, schema: &Schema) -> Vec<Index> {
+        if !self.is_soft_delete() {
+            return vec![];
+        }
+
+        let unique_fields = self
+            .fields
+            .iter()
+            .filter(|field| {
+                field.properties.contains(&Property::Unique)
+                    && !schema.is_foreign_key_target(self, field)
+            })
+            .map(|field| {
+                (
+                    vec![FieldName::new(
+                        field.name.clone(),
+                        field.line_number,
+                        field.column,
+                    )],
+                    field.line_number,
+                    field.column,
+                )
+            });
+        let unique_constraints = self
+            .constraints
+            .iter()
+            .filter(|constraint| constraint.kind.eq(&ConstraintKind::Unique))
+            .map(|constraint| {
+                (
+                    constraint.fields.clone(),
+                    constraint.line_number,
+                    constraint.column,
+                )
+            });
+
+        unique_fields
+            .chain(unique_constraints)
+            .map(|(columns, line_number, column)| {
+                let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+                let mut index = Index::new(
+                    Some(format!("{}_{}_unique", self.name, names.join("_"))),
+                    self.name.clone(),
+                    columns,
+                    true,
+                    line_number,
+                    column,
+                );
+                index.file = self.file.clone();
+                index
+            })
+            .collect()
+    }
+
+    // The condition selecting rows which are not soft deleted.
+    fn active_condition(&self) -> Option<String> {
+        if !self.is_soft_delete() {
+            return None;
+        }
+
+        let column = self
+            .get_field(SOFT_DELETE_FIELD)
+            .map_or(SOFT_DELETE_FIELD, |field| field.column_name());
+
+        Some(format!("{} IS NULL", quote_identifier(column)))
+    }
+
+    pub fn active_view_to_sql(&self) -> Option<String> {
+        let condition = self.active_condition()?;
+        let table = quote_identifier(self.table_name());
+
+        Some(format!(
+            "CREATE VIEW IF NOT EXISTS {} AS\nSELECT *\nFROM {}\nWHERE {};",
+            quote_identifier(&format!("{}_active", self.table_name())),
+            table,
+            condition
+        ))
+    }
+
+    fn check_soft_delete(&self) -> Result<(), ToSQLError> {
+        let option = match self.get_option(&ModelOptionKind::SoftDelete) {
+            Some(option) => option,
+            None => return Ok(()),
+        };
+
+        match self.get_field(SOFT_DELETE_FIELD) {
+            Some(field)
+                if field
+                    .data_type
+                    .data_type
+                    .ne(&DataType::Optional(Box::new(DataType::Timestamp))) =>
+            {
+                Err(ToSQLError::InvalidModelOption {
+                    model_name: self.name.clone(),
+                    option: option.kind.to_string(),
+                    reason: format!(
+                        "'{}' must be an optional timestamp, got {}",
+                        SOFT_DELETE_FIELD, field.data_type.data_type
+                    ),
+                    line_number: field.line_number,
+                    column: field.column,
+                }
+                .in_file(&field.file))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_unique_field(&self, field: &Field, schema: &Schema) -> bool {
+        let is_field = |fields: &[FieldName]| matches!(fields, [only] if only.name.eq(&field.name));
+
+        field.properties.contains(&Property::PrimaryKey)
+            || field.properties.contains(&Property::Unique)
+            || self.constraints.iter().any(|constraint| {
+                matches!(
+                    constraint.kind,
+                    ConstraintKind::PrimaryKey | ConstraintKind::Unique
+                ) && is_field(&constraint.fields)
+            })
+            || schema.indexes.iter().any(|index| {
+                index.unique
+                    && index.condition.is_none()
+                    && index.model.eq(&self.name)
+                    && is_field(&index.columns)
+            })
+    }
+}
+
+impl JoinKind {
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            JoinKind::Inner => "JOIN",
+            JoinKind::Left => "LEFT JOIN",
+        }
+    }
+}
+
+impl ModelConstraint {
+    pub fn to_sql(&self, model: &Model, schema: &Schema) -> Result<String, ToSQLError> {
+        let fields = model.field_names_to_sql(&self.fields)?;
+        let constraint = match &self.kind {
+            ConstraintKind::PrimaryKey => "PRIMARY KEY",
+            ConstraintKind::Unique => "UNIQUE",
+            ConstraintKind::Check(expression) => {
+                model.check_condition(expression, schema)?;
+                return Ok(format!("    CHECK({})", expression.to_sql(model, schema)?));
+            }
+        };
+
+        Ok(format!("    {} ({})", constraint, fields.join(", ")))
     }
 }
 
 impl Property {
-    pub fn to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
+    pub fn to_sql(&self, model: &Model, schema: &Schema) -> Result<String, ToSQLError> {
         match &self {
             Property::PrimaryKey => Ok("PRIMARY KEY".to_string()),
             Property::AutoIncrement => Ok("AUTOINCREMENT".to_string()),
             Property::Unique => Ok("UNIQUE".to_string()),
             Property::FunctionCall(func) => func.to_sql(schema),
+            Property::Check(expression) => {
+                Ok(format!("CHECK({})", expression.to_sql(model, schema)?))
+            }
+            Property::Generated { expression, stored } => Ok(format!(
+                "GENERATED ALWAYS AS ({}) {}",
+                expression.to_sql(model, schema)?,
+                if *stored { "STORED" } else { "VIRTUAL" }
+            )),
+        }
+    }
+}
+
+impl Expression {
+    // Fields are rendered as the columns of the model the expression is
+    // declared on.
+    pub fn to_sql(&self, model: &Model, schema: &Schema) -> Result<String, ToSQLError> {
+        let precedence = self.precedence();
+
+        match &self.kind {
+            ExpressionKind::Value(value) => Ok(value.to_sql()),
+            ExpressionKind::Null => Ok("NULL".to_string()),
+            ExpressionKind::Field(name) => Ok(quote_identifier(
+                model
+                    .get_field(name)
+                    .map_or(name.as_str(), |field| field.column_name()),
+            )),
+            ExpressionKind::Reference(reference) => reference.variant_reference_to_sql(schema),
+            ExpressionKind::FunctionCall(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| argument.to_sql(model, schema))
+                    .collect::<Result<Vec<String>, ToSQLError>>()?;
+
+                Ok(format!("{}({})", name, arguments.join(", ")))
+            }
+            ExpressionKind::Unary(UnaryOperator::Not, operand) => Ok(format!(
+                "NOT {}",
+                operand.operand_to_sql(precedence, model, schema)?
+            )),
+            ExpressionKind::Unary(UnaryOperator::Negate, operand) => {
+                let operand = operand.numeric_operand_to_sql(precedence, model, schema)?;
+
+                // `--` would start a comment
+                if operand.starts_with('-') {
+                    Ok(format!("-({})", operand))
+                } else {
+                    Ok(format!("-{}", operand))
+                }
+            }
+            ExpressionKind::Binary(
+                left,
+                operator @ (BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Concat),
+                right,
+            ) => Ok(format!(
+                "{} {} {}",
+                left.operand_to_sql(precedence, model, schema)?,
+                operator.to_sql(),
+                right.operand_to_sql(precedence + 1, model, schema)?
+            )),
+            ExpressionKind::Binary(left, operator, right) => Ok(format!(
+                "{} {} {}",
+                left.numeric_operand_to_sql(precedence, model, schema)?,
+                operator.to_sql(),
+                right.numeric_operand_to_sql(precedence + 1, model, schema)?
+            )),
+            ExpressionKind::In {
+                expression,
+                list,
+                negated,
+            } => {
+                let list = list
+                    .iter()
+                    .map(|item| item.numeric_operand_to_sql(0, model, schema))
+                    .collect::<Result<Vec<String>, ToSQLError>>()?;
+
+                Ok(format!(
+                    "{} {}IN ({})",
+                    expression.numeric_operand_to_sql(precedence + 1, model, schema)?,
+                    if *negated { "NOT " } else { "" },
+                    list.join(", ")
+                ))
+            }
+            ExpressionKind::Like {
+                expression,
+                pattern,
+                negated,
+            } => Ok(format!(
+                "{} {}LIKE {}",
+                expression.operand_to_sql(precedence + 1, model, schema)?,
+                if *negated { "NOT " } else { "" },
+                pattern.operand_to_sql(precedence + 1, model, schema)?
+            )),
+            ExpressionKind::IsNull {
+                expression,
+                negated,
+            } => Ok(format!(
+                "{} IS {}NULL",
+                expression.operand_to_sql(precedence + 1, model, schema)?,
+                if *negated { "NOT " } else { "" }
+            )),
+        }
+    }
+
+    fn operand_to_sql(
+        &self,
+        precedence: u8,
+        model: &Model,
+        schema: &Schema,
+    ) -> Result<String, ToSQLError> {
+        let sql = self.to_sql(model, schema)?;
+
+        if self.precedence() < precedence {
+            Ok(format!("({})", sql))
+        } else {
+            Ok(sql)
+        }
+    }
+
+    // Decimals are stored as text, they are compared and computed as numbers.
+    fn numeric_operand_to_sql(
+        &self,
+        precedence: u8,
+        model: &Model,
+        schema: &Schema,
+    ) -> Result<String, ToSQLError> {
+        match &self.kind {
+            ExpressionKind::Field(name)
+                if model.get_field(name).is_some_and(|field| {
+                    matches!(
+                        field.data_type.data_type.without_optional(),
+                        DataType::Decimal { .. }
+                    )
+                }) =>
+            {
+                Ok(format!("CAST({} AS REAL)", self.to_sql(model, schema)?))
+            }
+            _ => self.operand_to_sql(precedence, model, schema),
+        }
+    }
+}
+
+impl BinaryOperator {
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            BinaryOperator::Or => "OR",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Equal => "=",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanOrEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanOrEqual => ">=",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+            BinaryOperator::Concat => "||",
         }
     }
 }
@@ -89,6 +1365,9 @@ impl Property {
 impl Reference {
     pub fn field_reference_to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
         match schema.get_model(&self.entity) {
+            Some(model) if model.is_abstract => {
+                Err(model.abstract_model(self.line_number, self.column))
+            }
             Some(model) => model.field_to_sql(&self.property, self.line_number, self.column),
             None => Err(ToSQLError::ModelNotFound {
                 model_name: self.entity.clone(),
@@ -98,6 +1377,37 @@ impl Reference {
         }
     }
 
+    // Renders `model.field` as a qualified column, as used by views.
+    pub fn column_reference_to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
+        let model = match schema.get_model(&self.entity) {
+            Some(model) if model.is_abstract => {
+                return Err(model.abstract_model(self.line_number, self.column))
+            }
+            Some(model) => model,
+            None => {
+                return Err(ToSQLError::ModelNotFound {
+                    model_name: self.entity.clone(),
+                    line_number: self.line_number,
+                    column: self.column,
+                })
+            }
+        };
+
+        match model.get_field(&self.property) {
+            Some(field) if !field.is_relation() => Ok(format!(
+                "{}.{}",
+                quote_identifier(model.table_name()),
+                quote_identifier(field.column_name())
+            )),
+            _ => Err(ToSQLError::FieldNotFound {
+                model_name: model.name.clone(),
+                field_name: self.property.clone(),
+                line_number: self.line_number,
+                column: self.column,
+            }),
+        }
+    }
+
     pub fn variant_reference_to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
         match schema.get_enum(&self.entity) {
             Some(e) => e.variant_to_sql(&self.property, self.line_number, self.column),
@@ -118,8 +1428,12 @@ impl Model {
         column: usize,
     ) -> Result<String, ToSQLError> {
         match self.get_field(field_name) {
-            Some(_) => Ok(format!("{}({})", self.name, field_name)),
-            None => Err(ToSQLError::FieldNotFound {
+            Some(field) if !field.is_relation() => Ok(format!(
+                "{}({})",
+                quote_identifier(self.table_name()),
+                quote_identifier(field.column_name())
+            )),
+            _ => Err(ToSQLError::FieldNotFound {
                 field_name: field_name.to_string(),
                 model_name: self.name.to_string(),
                 line_number,
@@ -129,6 +1443,131 @@ impl Model {
     }
 }
 
+impl Model {
+    fn abstract_model(&self, line_number: usize, column: usize) -> ToSQLError {
+        ToSQLError::AbstractModel {
+            model_name: self.name.clone(),
+            line_number,
+            column,
+        }
+    }
+
+    pub fn field_names_to_sql(&self, field_names: &[FieldName]) -> Result<Vec<String>, ToSQLError> {
+        field_names
+            .iter()
+            .map(|field_name| match self.get_field(&field_name.name) {
+                Some(field) if !field.is_relation() => Ok(quote_identifier(field.column_name())),
+                _ => Err(ToSQLError::FieldNotFound {
+                    model_name: self.name.clone(),
+                    field_name: field_name.name.clone(),
+                    line_number: field_name.line_number,
+                    column: field_name.column,
+                }),
+            })
+            .collect()
+    }
+
+    // Fields mapped with `map("name")` must not collide with other columns.
+    fn check_column_names(&self) -> Result<(), ToSQLError> {
+        let columns: Vec<&Field> = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_relation())
+            .collect();
+
+        for (i, field) in columns.iter().enumerate() {
+            if let Some(other) = columns[..i]
+                .iter()
+                .find(|other| other.column_name().eq(field.column_name()))
+            {
+                return Err(ToSQLError::InvalidProperty {
+                    model_name: self.name.clone(),
+                    field_name: field.name.clone(),
+                    reason: format!(
+                        "column '{}' is already used by field '{}'",
+                        field.column_name(),
+                        other.name
+                    ),
+                    line_number: field.line_number,
+                    column: field.column,
+                }
+                .in_file(&field.file));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Tables without a rowid are keyed by their primary key, which is
+    // required and cannot auto increment.
+    fn check_without_rowid(&self) -> Result<(), ToSQLError> {
+        let option = match self.get_option(&ModelOptionKind::WithoutRowid) {
+            Some(option) => option,
+            None => return Ok(()),
+        };
+
+        let invalid_option = |reason: &str, line_number: usize, column: usize| {
+            Err(ToSQLError::InvalidModelOption {
+                model_name: self.name.clone(),
+                option: option.kind.to_string(),
+                reason: reason.to_string(),
+                line_number,
+                column,
+            })
+        };
+
+        if let Some(field) = self
+            .fields
+            .iter()
+            .find(|field| field.properties.contains(&Property::AutoIncrement))
+        {
+            return invalid_option(
+                &format!("'{}' cannot use auto_increment", field.name),
+                field.line_number,
+                field.column,
+            )
+            .map_err(|error| error.in_file(&field.file));
+        }
+
+        if self.row_key().eq(&["rowid"]) {
+            return invalid_option(
+                "a primary key is required",
+                option.line_number,
+                option.column,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_primary_keys(&self) -> Result<(), ToSQLError> {
+        let primary_keys: Vec<(usize, usize, &Option<String>)> = self
+            .fields
+            .iter()
+            .filter(|field| field.properties.contains(&Property::PrimaryKey))
+            .map(|field| (field.line_number, field.column, &field.file))
+            .chain(
+                self.constraints
+                    .iter()
+                    .filter(|constraint| constraint.kind.eq(&ConstraintKind::PrimaryKey))
+                    .map(|constraint| {
+                        (constraint.line_number, constraint.column, &constraint.file)
+                    }),
+            )
+            .collect();
+
+        match primary_keys.get(1) {
+            Some((line_number, column, file)) => Err(ToSQLError::MultiplePrimaryKeys {
+                model_name: self.name.clone(),
+                line_number: *line_number,
+                column: *column,
+            }
+            .in_file(file)),
+            None => Ok(()),
+        }
+    }
+}
+
 impl Enum {
     pub fn variant_to_sql(
         &self,
@@ -137,7 +1576,7 @@ impl Enum {
         column: usize,
     ) -> Result<String, ToSQLError> {
         match self.get_variant(variant) {
-            Some(_) => Ok(format!("'{}'", variant)),
+            Some(variant) => Ok(variant.to_sql()),
             None => Err(ToSQLError::VariantNotFound {
                 variant: variant.to_string(),
                 enum_name: self.name.to_string(),
@@ -150,7 +1589,7 @@ impl Enum {
 
 impl EnumVariant {
     pub fn to_sql(&self) -> String {
-        self.name.to_string()
+        self.value.to_sql()
     }
 }
 
@@ -159,13 +1598,34 @@ impl EnumVariant {
 impl FunctionCall {
     pub fn to_sql(&self, schema: &Schema) -> Result<String, ToSQLError> {
         match self.name.as_str() {
-            "now" => Ok("CURRENT_TIMESTAMP".to_string()),
+            "now" => Ok(self
+                .context
+                .property_data_type
+                .data_type
+                .without_optional()
+                .now_to_sql()),
+            "uuid" => Ok(rayql::sql::function::uuid()),
             "min" => rayql::sql::function::min(schema, &self.context, &self.arguments),
             "max" => rayql::sql::function::max(schema, &self.context, &self.arguments),
             "references" => {
                 rayql::sql::function::references(schema, &self.arguments, &self.context)
             }
             "default" => rayql::sql::function::default(schema, &self.arguments, &self.context),
+            "collate" => rayql::sql::function::collate(schema, &self.arguments, &self.context),
+            "on_update" => rayql::sql::function::on_update(schema, &self.arguments, &self.context),
+            "pattern" | "like" | "length" | "between" | "email" | "url" => {
+                match self.validation()? {
+                    Some(validation) => Ok(format!("CHECK({})", validation.to_sql(&self.context))),
+                    None => unreachable!("{} is a validator", self.name),
+                }
+            }
+            "relation" => Err(ToSQLError::FunctionError {
+                source: FunctionError::InvalidArgument(
+                    "relation can only be used on fields referencing a model".to_string(),
+                ),
+                line_number: self.line_number,
+                column: self.column,
+            }),
             _ => Err(ToSQLError::FunctionError {
                 source: FunctionError::UndefinedFunction(self.name.clone()),
                 line_number: self.line_number,
@@ -175,6 +1635,70 @@ impl FunctionCall {
     }
 }
 
+impl FunctionCall {
+    pub fn validation(&self) -> Result<Option<Validation>, ToSQLError> {
+        rayql::sql::function::validation(&self.name, &self.arguments, &self.context)
+    }
+}
+
+impl Field {
+    pub fn validations(&self) -> Result<Vec<Validation>, ToSQLError> {
+        self.properties
+            .iter()
+            .filter_map(|property| match property {
+                Property::FunctionCall(func) => func.validation().transpose(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Validation {
+    pub fn to_sql(&self, context: &FunctionCallContext) -> String {
+        let column = &quote_identifier(&context.column_name);
+
+        match self {
+            Validation::Pattern(pattern) => format!(
+                "{} GLOB {}",
+                column,
+                Value::StringLiteral(pattern.clone()).to_sql()
+            ),
+            Validation::Like(pattern) => format!(
+                "{} LIKE {}",
+                column,
+                Value::StringLiteral(pattern.clone()).to_sql()
+            ),
+            Validation::Length {
+                min: Some(min),
+                max: Some(max),
+            } => format!("length({}) BETWEEN {} AND {}", column, min, max),
+            Validation::Length { min: Some(min), .. } => format!("length({}) >= {}", column, min),
+            Validation::Length { max: Some(max), .. } => format!("length({}) <= {}", column, max),
+            Validation::Length { .. } => unreachable!("length has at least one bound"),
+            // decimals are stored as text and compared as numbers
+            Validation::Between(Value::StringLiteral(low), Value::StringLiteral(high))
+                if matches!(
+                    context.property_data_type.data_type.without_optional(),
+                    DataType::Decimal { .. }
+                ) =>
+            {
+                format!("CAST({} AS REAL) BETWEEN {} AND {}", column, low, high)
+            }
+            Validation::Between(low, high) => {
+                format!("{} BETWEEN {} AND {}", column, low.to_sql(), high.to_sql())
+            }
+            Validation::Email => format!(
+                "{0} GLOB '?*@?*.?*' AND {0} NOT GLOB '*@*@*' AND {0} NOT GLOB '* *'",
+                column
+            ),
+            Validation::Url => format!(
+                "({0} GLOB 'http://?*' OR {0} GLOB 'https://?*') AND {0} NOT GLOB '* *'",
+                column
+            ),
+        }
+    }
+}
+
 impl Arguments {
     pub fn to_sql(&self, schema: &Schema) -> Result<Vec<String>, ToSQLError> {
         self.list.iter().map(|arg| arg.to_sql(schema)).collect()
@@ -201,7 +1725,7 @@ impl ArgumentValue {
 impl Value {
     pub fn to_sql(&self) -> String {
         match self {
-            Value::StringLiteral(s) => format!("'{}'", s),
+            Value::StringLiteral(s) => quote_string(s),
             Value::Integer(i) => i.to_string(),
             Value::Real(f) => {
                 if f.fract().eq(&0.0) {
@@ -222,18 +1746,255 @@ impl Value {
 }
 
 impl DataType {
-    pub fn to_sql(&self, not_null: bool) -> String {
+    // STRICT tables only accept the INTEGER, REAL, TEXT, BLOB and ANY types.
+    pub fn to_sql(&self, not_null: bool, strict: bool) -> String {
         let null_suffix = if not_null { "NOT NULL" } else { "NULL" };
         let data_type = match &self {
-            DataType::String | DataType::Enum(_) => "TEXT",
+            DataType::String
+            | DataType::Enum(_)
+            | DataType::Json(_)
+            | DataType::Date
+            | DataType::Time
+            | DataType::Uuid
+            | DataType::Decimal { .. } => "TEXT",
             DataType::Integer => "INTEGER",
             DataType::Real => "REAL",
             DataType::Blob => "BLOB",
+            DataType::Boolean if strict => "INTEGER",
             DataType::Boolean => "BOOLEAN",
+            DataType::Timestamp if strict => "TEXT",
             DataType::Timestamp => "TIMESTAMP",
-            DataType::Optional(inner_type) => return inner_type.to_sql(false),
+            DataType::Optional(inner_type) => return inner_type.to_sql(false, strict),
+            DataType::Model(_) | DataType::List(_) => {
+                unreachable!("relation fields do not map to a column")
+            }
         };
 
         format!("{} {}", data_type, null_suffix)
     }
+
+    // The current date and time in the format of the column.
+    fn now_to_sql(&self) -> String {
+        match self {
+            DataType::Date => "CURRENT_DATE",
+            DataType::Time => "CURRENT_TIME",
+            _ => "CURRENT_TIMESTAMP",
+        }
+        .to_string()
+    }
+
+    // Dates, times, uuids and decimals are stored as text, the CHECK keeps
+    // the text in the format the type expects.
+    pub fn format_check_to_sql(&self, column: &str) -> Option<String> {
+        match self {
+            // the modifier makes SQLite normalize days past the end of the month
+            DataType::Date => Some(format!("{0} IS date({0}, '+0 days')", column)),
+            DataType::Time => Some(format!("{0} IS time({0})", column)),
+            DataType::Uuid => {
+                let pattern = [8, 4, 4, 4, 12]
+                    .map(|len| "[0-9a-fA-F]".repeat(len))
+                    .join("-");
+                Some(format!("{} GLOB '{}'", column, pattern))
+            }
+            DataType::Decimal { precision, scale } => Some(format!(
+                "({0} GLOB '[0-9]*' OR {0} GLOB '-[0-9]*') AND {0} GLOB '*[0-9]' \
+                 AND substr({0}, 2) NOT GLOB '*[^0-9.]*' AND {0} NOT GLOB '*.*.*' \
+                 AND length(ltrim({0}, '-0')) - length(ltrim({0}, '-0123456789')) <= {1} \
+                 AND length(ltrim({0}, '-0123456789')) <= {2}",
+                column,
+                precision - scale,
+                scale + 1
+            )),
+            DataType::Optional(inner) => inner.format_check_to_sql(column),
+            _ => None,
+        }
+    }
+}
+
+fn doc_comment_to_sql(doc: &Option<String>, indent: &str) -> String {
+    match doc {
+        Some(doc) => doc
+            .lines()
+            .map(|line| format!("{}-- {}\n", indent, line).replace("-- \n", "--\n"))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+// Keywords of SQLite, names matching one of them have to be quoted.
+// https://www.sqlite.org/lang_keywords.html
+const RESERVED_WORDS: [&str; 147] = [
+    "ABORT",
+    "ACTION",
+    "ADD",
+    "AFTER",
+    "ALL",
+    "ALTER",
+    "ALWAYS",
+    "ANALYZE",
+    "AND",
+    "AS",
+    "ASC",
+    "ATTACH",
+    "AUTOINCREMENT",
+    "BEFORE",
+    "BEGIN",
+    "BETWEEN",
+    "BY",
+    "CASCADE",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "COMMIT",
+    "CONFLICT",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "DATABASE",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DEFERRED",
+    "DELETE",
+    "DESC",
+    "DETACH",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "EACH",
+    "ELSE",
+    "END",
+    "ESCAPE",
+    "EXCEPT",
+    "EXCLUDE",
+    "EXCLUSIVE",
+    "EXISTS",
+    "EXPLAIN",
+    "FAIL",
+    "FILTER",
+    "FIRST",
+    "FOLLOWING",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GENERATED",
+    "GLOB",
+    "GROUP",
+    "GROUPS",
+    "HAVING",
+    "IF",
+    "IGNORE",
+    "IMMEDIATE",
+    "IN",
+    "INDEX",
+    "INDEXED",
+    "INITIALLY",
+    "INNER",
+    "INSERT",
+    "INSTEAD",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "ISNULL",
+    "JOIN",
+    "KEY",
+    "LAST",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "MATCH",
+    "MATERIALIZED",
+    "NATURAL",
+    "NO",
+    "NOT",
+    "NOTHING",
+    "NOTNULL",
+    "NULL",
+    "NULLS",
+    "OF",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OTHERS",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "PLAN",
+    "PRAGMA",
+    "PRECEDING",
+    "PRIMARY",
+    "QUERY",
+    "RAISE",
+    "RANGE",
+    "RECURSIVE",
+    "REFERENCES",
+    "REGEXP",
+    "REINDEX",
+    "RELEASE",
+    "RENAME",
+    "REPLACE",
+    "RESTRICT",
+    "RETURNING",
+    "RIGHT",
+    "ROLLBACK",
+    "ROW",
+    "ROWS",
+    "SAVEPOINT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "TEMP",
+    "TEMPORARY",
+    "THEN",
+    "TIES",
+    "TO",
+    "TRANSACTION",
+    "TRIGGER",
+    "UNBOUNDED",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VACUUM",
+    "VALUES",
+    "VIEW",
+    "VIRTUAL",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+    "WITHOUT",
+];
+
+pub fn is_reserved_word(word: &str) -> bool {
+    RESERVED_WORDS
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(word))
+}
+
+// Renders the name of a table, column, index, view or trigger, quoting it
+// when it is a reserved word or not a plain identifier.
+pub fn quote_identifier(identifier: &str) -> String {
+    let is_plain = identifier.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+        && identifier
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+
+    if is_plain && !is_reserved_word(identifier) {
+        identifier.to_string()
+    } else {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+}
+
+// Renders a string literal, embedded quotes are doubled.
+pub fn quote_string(string: &str) -> String {
+    format!("'{}'", string.replace('\'', "''"))
 }
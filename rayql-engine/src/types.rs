@@ -1,3 +1,5 @@
+use rayql::Value;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum DataType {
     String,
@@ -6,8 +8,16 @@ pub enum DataType {
     Blob,
     Boolean,
     Timestamp,
+    Date,
+    Time,
+    Uuid,
+    Decimal { precision: u32, scale: u32 },
+    // The optional name of the struct describing the shape of the document.
+    Json(Option<String>),
     Optional(Box<DataType>),
     Enum(String),
+    Model(String),
+    List(Box<DataType>),
 }
 
 impl std::fmt::Display for DataType {
@@ -19,8 +29,173 @@ impl std::fmt::Display for DataType {
             DataType::Blob => write!(f, "Blob"),
             DataType::Boolean => write!(f, "Boolean"),
             DataType::Timestamp => write!(f, "Timestamp"),
+            DataType::Date => write!(f, "Date"),
+            DataType::Time => write!(f, "Time"),
+            DataType::Uuid => write!(f, "Uuid"),
+            DataType::Decimal { precision, scale } => {
+                write!(f, "Decimal({}, {})", precision, scale)
+            }
+            DataType::Json(None) => write!(f, "Json"),
+            DataType::Json(Some(shape)) => write!(f, "Json<{}>", shape),
             DataType::Optional(inner) => write!(f, "Optional<{}>", inner),
             DataType::Enum(name) => write!(f, "Enum({})", name),
+            DataType::Model(name) => write!(f, "Model({})", name),
+            DataType::List(inner) => write!(f, "List<{}>", inner),
+        }
+    }
+}
+
+impl DataType {
+    // The type of the value when it is present.
+    pub fn without_optional(&self) -> &DataType {
+        match self {
+            DataType::Optional(inner) => inner.without_optional(),
+            data_type => data_type,
+        }
+    }
+
+    pub fn is_relation(&self) -> bool {
+        match self {
+            DataType::Model(_) | DataType::List(_) => true,
+            DataType::Optional(inner) => inner.is_relation(),
+            _ => false,
+        }
+    }
+
+    pub fn is_json(&self) -> bool {
+        match self {
+            DataType::Json(_) => true,
+            DataType::Optional(inner) => inner.is_json(),
+            _ => false,
+        }
+    }
+
+    pub fn get_json_shape(&self) -> Option<&str> {
+        match self {
+            DataType::Json(shape) => shape.as_deref(),
+            DataType::Optional(inner) => inner.get_json_shape(),
+            _ => None,
+        }
+    }
+
+    // Types stored as text which has to follow a specific format.
+    pub fn is_formatted_text(&self) -> bool {
+        match self {
+            DataType::Date | DataType::Time | DataType::Uuid | DataType::Decimal { .. } => true,
+            DataType::Optional(inner) => inner.is_formatted_text(),
+            _ => false,
+        }
+    }
+
+    // Checks that a literal follows the format of the type, returning the text
+    // which is stored in the column.
+    pub fn format_literal(&self, value: &Value) -> Result<String, String> {
+        match (self, value) {
+            (DataType::Optional(inner), value) => inner.format_literal(value),
+            (DataType::Date, Value::StringLiteral(date)) if is_date(date) => Ok(date.clone()),
+            (DataType::Date, _) => Err("expected a date in the format YYYY-MM-DD".to_string()),
+            (DataType::Time, Value::StringLiteral(time)) if is_time(time) => Ok(time.clone()),
+            (DataType::Time, _) => Err("expected a time in the format HH:MM:SS".to_string()),
+            (DataType::Uuid, Value::StringLiteral(uuid)) if is_uuid(uuid) => Ok(uuid.clone()),
+            (DataType::Uuid, _) => Err(
+                "expected a uuid in the format xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx".to_string(),
+            ),
+            (DataType::Decimal { precision, scale }, value) => {
+                let decimal = match value {
+                    Value::StringLiteral(decimal) => decimal.clone(),
+                    Value::Integer(integer) => integer.to_string(),
+                    Value::Real(real) => real.to_string(),
+                    _ => return Err("expected a decimal number".to_string()),
+                };
+
+                if is_decimal(&decimal, *precision, *scale) {
+                    Ok(decimal)
+                } else {
+                    Err(format!(
+                        "expected a decimal number with at most {} digits before and {} digits after the decimal point",
+                        precision - scale,
+                        scale
+                    ))
+                }
+            }
+            (data_type, _) => Err(format!("{} is not stored as formatted text", data_type)),
+        }
+    }
+
+    pub fn get_enum_name(&self) -> Option<&str> {
+        match self {
+            DataType::Enum(name) => Some(name),
+            DataType::Optional(inner) => inner.get_enum_name(),
+            _ => None,
         }
     }
 }
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|ch| ch.is_ascii_digit())
+}
+
+fn is_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    let (year, month, day) = match parts[..] {
+        [year, month, day]
+            if year.len() == 4
+                && month.len() == 2
+                && day.len() == 2
+                && [year, month, day].iter().all(|part| is_digits(part)) =>
+        {
+            (
+                year.parse::<u32>().unwrap(),
+                month.parse::<u32>().unwrap(),
+                day.parse::<u32>().unwrap(),
+            )
+        }
+        _ => return false,
+    };
+
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return false,
+    };
+
+    (1..=days_in_month).contains(&day)
+}
+
+fn is_time(time: &str) -> bool {
+    let parts: Vec<&str> = time.split(':').collect();
+    match parts[..] {
+        [hours, minutes, seconds] => {
+            [(hours, 24), (minutes, 60), (seconds, 60)]
+                .iter()
+                .all(|(part, limit)| {
+                    part.len() == 2 && is_digits(part) && part.parse::<u32>().unwrap() < *limit
+                })
+        }
+        _ => false,
+    }
+}
+
+fn is_uuid(uuid: &str) -> bool {
+    let groups: Vec<&str> = uuid.split('-').collect();
+    groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+        && groups
+            .iter()
+            .all(|group| group.chars().all(|ch| ch.is_ascii_hexdigit()))
+}
+
+fn is_decimal(decimal: &str, precision: u32, scale: u32) -> bool {
+    let unsigned = decimal.strip_prefix('-').unwrap_or(decimal);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) if is_digits(fraction) => (integer, fraction),
+        Some(_) => return false,
+        None => (unsigned, ""),
+    };
+
+    is_digits(integer)
+        && integer.len() as u32 <= precision - scale
+        && fraction.len() as u32 <= scale
+}
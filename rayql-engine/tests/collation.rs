@@ -0,0 +1,62 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const USER: &str = "model user {
+    id: int primary_key,
+    username: str unique collate(nocase),
+    code: str collate(rtrim),
+    index(code),
+}";
+
+#[test]
+fn collations_apply_to_columns_and_indexes() {
+    let sql = schema_to_sql(USER).unwrap();
+
+    assert_eq!(
+        sql,
+        [
+            "CREATE TABLE IF NOT EXISTS user (
+    id INTEGER NOT NULL PRIMARY KEY,
+    username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    code TEXT NOT NULL COLLATE RTRIM
+);",
+            "CREATE INDEX IF NOT EXISTS user_code_idx ON user (code COLLATE RTRIM);",
+        ]
+    );
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(&sql.join("\n")).unwrap();
+    connection
+        .execute_batch("INSERT INTO user VALUES (1, 'Ray', 'a');")
+        .unwrap();
+    assert!(connection
+        .execute_batch("INSERT INTO user VALUES (2, 'RAY', 'b');")
+        .is_err());
+}
+
+#[test]
+fn collations_are_validated() {
+    for (from, to, error) in [
+        (
+            "id: int primary_key,",
+            "id: int primary_key collate(nocase),",
+            "Function error: Invalid argument: collate can only be used on text fields, 'id' is Integer at line 2, column 25",
+        ),
+        (
+            "collate(rtrim)",
+            "collate(latin)",
+            "Function error: Invalid argument: collate must be one of nocase, binary or rtrim, got Identifier(\"latin\") at line 4, column 23",
+        ),
+    ] {
+        assert_eq!(
+            schema_to_sql(&USER.replace(from, to)).unwrap_err(),
+            error,
+            "{}",
+            to
+        );
+    }
+}
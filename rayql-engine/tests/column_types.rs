@@ -0,0 +1,92 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const INVOICE: &str = "model invoice {
+    id: uuid primary_key default(uuid()),
+    issued_on: date default(now()),
+    at: time?,
+    total: decimal(5, 2) default('0.00'),
+}";
+
+fn invoice_table() -> rusqlite::Connection {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(&schema_to_sql(INVOICE).unwrap().join("\n"))
+        .unwrap();
+    connection
+}
+
+#[test]
+fn defaults_are_valid_values() {
+    let connection = invoice_table();
+    connection
+        .execute("INSERT INTO invoice DEFAULT VALUES", [])
+        .unwrap();
+
+    let (id, total): (String, String) = connection
+        .query_row("SELECT id, total FROM invoice", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(id.len(), 36);
+    assert_eq!(&id[14..15], "4");
+    assert_eq!(total, "0.00");
+}
+
+#[test]
+fn values_are_checked_by_the_database() {
+    let connection = invoice_table();
+    let insert = |column: &str, value: &str| {
+        connection
+            .execute(
+                &format!("INSERT INTO invoice ({}) VALUES (?1)", column),
+                [value],
+            )
+            .is_ok()
+    };
+
+    assert!(insert("issued_on", "2024-02-29"));
+    assert!(!insert("issued_on", "2023-02-29"));
+    assert!(!insert("issued_on", "29/02/2024"));
+    assert!(insert("at", "23:59:59"));
+    assert!(!insert("at", "25:00:00"));
+    assert!(!insert("at", "12:00"));
+    assert!(insert("id", "0b6a3c4e-5f1d-4c2b-9a8e-7d6f5e4c3b2a"));
+    assert!(!insert("id", "not-a-uuid"));
+    assert!(insert("total", "-123.45"));
+    assert!(!insert("total", "1234.5"));
+    assert!(!insert("total", "1.234"));
+    assert!(!insert("total", "1e3"));
+}
+
+#[test]
+fn literals_are_checked_at_compile_time() {
+    for (from, to, error) in [
+        (
+            "default('0.00')",
+            "default('1.234')",
+            "Function error: Invalid argument: expected a decimal number with at most 3 digits before and 2 digits after the decimal point at line 5, column 35",
+        ),
+        (
+            "time?",
+            "time? default('25:00')",
+            "Function error: Invalid argument: expected a time in the format HH:MM:SS at line 4, column 24",
+        ),
+    ] {
+        assert_eq!(
+            schema_to_sql(&INVOICE.replace(from, to)).unwrap_err(),
+            error,
+            "{}",
+            to
+        );
+    }
+
+    assert_eq!(
+        schema_to_sql(&INVOICE.replace("decimal(5, 2)", "decimal(2, 3)")).unwrap_err(),
+        "Invalid data type 'decimal(2, 3)': the precision must be at least 1 and not less than the scale"
+    );
+}
@@ -0,0 +1,80 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const MEMBERSHIP: &str = "model membership {
+    team_id: int,
+    user_id: int,
+    role: str,
+    primary_key(team_id, user_id),
+    unique(user_id, role),
+}";
+
+#[test]
+fn composite_constraints_are_rendered_on_the_table() {
+    let sql = schema_to_sql(MEMBERSHIP).unwrap();
+
+    assert_eq!(
+        sql,
+        ["CREATE TABLE IF NOT EXISTS membership (
+    team_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    role TEXT NOT NULL,
+    PRIMARY KEY (team_id, user_id),
+    UNIQUE (user_id, role)
+);"]
+    );
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(&sql[0]).unwrap();
+    connection
+        .execute_batch("INSERT INTO membership VALUES (1, 1, 'owner'), (2, 1, 'member');")
+        .unwrap();
+    assert!(connection
+        .execute_batch("INSERT INTO membership VALUES (1, 1, 'guest');")
+        .is_err());
+    assert!(connection
+        .execute_batch("INSERT INTO membership VALUES (3, 1, 'owner');")
+        .is_err());
+}
+
+#[test]
+fn constraint_fields_cannot_repeat() {
+    for (from, to, field, position, first) in [
+        (
+            "primary_key(team_id, user_id)",
+            "primary_key(team_id, team_id)",
+            "team_id",
+            (5, 26),
+            (5, 17),
+        ),
+        (
+            "unique(user_id, role)",
+            "unique(user_id, user_id)",
+            "user_id",
+            (6, 21),
+            (6, 12),
+        ),
+    ] {
+        let source = MEMBERSHIP.replace(from, to);
+
+        match rayql_engine::schema::parse(&source).unwrap_err() {
+            rayql_engine::schema::error::ParseError::FieldAlreadyInConstraint {
+                field: repeated,
+                model,
+                line_number,
+                column,
+                other_line_number,
+                other_column,
+            } => {
+                assert_eq!((repeated.as_str(), model.as_str()), (field, "membership"));
+                assert_eq!((line_number, column), position, "{}", to);
+                assert_eq!((other_line_number, other_column), first, "{}", to);
+            }
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+}
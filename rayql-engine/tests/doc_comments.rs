@@ -0,0 +1,55 @@
+const SOURCE: &str = "## Roles a user can have
+enum role {
+    ## full access
+    admin,
+    member,
+}
+
+## A registered user
+## with an account
+model user {
+    ## primary key
+    id: int primary_key,
+    # not documented
+    role: role,
+}";
+
+#[test]
+fn doc_comments_are_attached_to_declarations() {
+    let schema = rayql_engine::schema::parse(SOURCE).expect("schema should parse");
+
+    let role = schema.get_enum("role").unwrap();
+    assert_eq!(role.doc.as_deref(), Some("Roles a user can have"));
+    assert_eq!(role.variants[0].doc.as_deref(), Some("full access"));
+    assert_eq!(role.variants[1].doc, None);
+
+    let user = schema.get_model("user").unwrap();
+    assert_eq!(
+        user.doc.as_deref(),
+        Some("A registered user\nwith an account")
+    );
+    assert_eq!(
+        user.get_field("id").unwrap().doc.as_deref(),
+        Some("primary key")
+    );
+    assert_eq!(user.get_field("role").unwrap().doc, None);
+}
+
+#[test]
+fn doc_comments_are_emitted_as_sql_comments() {
+    let sql = rayql_engine::schema::parse(SOURCE)
+        .expect("schema should parse")
+        .to_sql()
+        .expect("schema should render");
+
+    assert_eq!(
+        sql,
+        ["-- A registered user
+-- with an account
+CREATE TABLE IF NOT EXISTS user (
+    -- primary key
+    id INTEGER NOT NULL PRIMARY KEY,
+    role TEXT NOT NULL CHECK(role IN ('admin', 'member'))
+);"]
+    );
+}
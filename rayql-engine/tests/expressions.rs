@@ -0,0 +1,129 @@
+fn check_to_sql(condition: &str) -> Result<String, String> {
+    let source = format!(
+        "model item {{
+            id: int primary_key,
+            a: int,
+            b: int,
+            c: int,
+            name: str,
+            price: decimal(10, 2),
+            check({}),
+        }}",
+        condition
+    );
+
+    let sql = rayql_engine::schema::parse(&source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())?;
+
+    Ok(sql[0]
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("CHECK("))
+        .and_then(|line| line.strip_suffix(')'))
+        .expect("the check should be rendered")
+        .to_string())
+}
+
+#[test]
+fn expressions_render_back_to_equivalent_sql() {
+    for (condition, sql) in [
+        ("a - (b - c) > 0", "a - (b - c) > 0"),
+        ("a - b - c > 0", "a - b - c > 0"),
+        ("(a + b) * c > 0", "(a + b) * c > 0"),
+        ("a + b * c > 0", "a + b * c > 0"),
+        ("not a = b", "NOT a = b"),
+        ("not (a = b or b = c)", "NOT (a = b OR b = c)"),
+        ("-(-a) > 0", "-(-a) > 0"),
+        ("-a > 0", "-a > 0"),
+        ("a = 1 or b = 2 and c = 3", "a = 1 OR b = 2 AND c = 3"),
+        ("(a = 1 or b = 2) and c = 3", "(a = 1 OR b = 2) AND c = 3"),
+        ("a not in (1, 2)", "a NOT IN (1, 2)"),
+        (
+            "name like 'a%' or name is null",
+            "name LIKE 'a%' OR name IS NULL",
+        ),
+        ("length(name) > 0", "length(name) > 0"),
+    ] {
+        assert_eq!(check_to_sql(condition).unwrap(), sql, "{}", condition);
+    }
+}
+
+#[test]
+fn decimals_are_compared_as_numbers() {
+    assert_eq!(
+        check_to_sql("price > 0 and price * a < 100").unwrap(),
+        "CAST(price AS REAL) > 0 AND CAST(price AS REAL) * a < 100"
+    );
+}
+
+#[test]
+fn ill_typed_expressions_are_rejected() {
+    for (condition, error) in [
+        (
+            "a = 'x'",
+            "Invalid expression: cannot compare Integer with String at line 8, column 24",
+        ),
+        (
+            "a + 1",
+            "Invalid expression: expected a condition, got Integer at line 8, column 19",
+        ),
+        (
+            "name * 2 > 0",
+            "Invalid expression: expected a number, got String at line 8, column 19",
+        ),
+        (
+            "a like 'x%'",
+            "Invalid expression: expected a string, got Integer at line 8, column 19",
+        ),
+        (
+            "price = 'x'",
+            "Invalid expression: cannot compare Decimal(10, 2) with String at line 8, column 28",
+        ),
+        (
+            "random() > 0",
+            "Invalid expression: function 'random' cannot be used in expressions at line 8, column 19",
+        ),
+    ] {
+        assert_eq!(check_to_sql(condition).unwrap_err(), error, "{}", condition);
+    }
+}
+
+#[test]
+fn enums_are_typed_as_the_values_they_store() {
+    let schema_to_sql = |check: &str| {
+        let source = format!(
+            "enum status: int {{ draft, published }}
+            enum kind {{ article, page }}
+
+            model post {{
+                id: int primary_key,
+                status: status,
+                kind: kind,
+                check({}),
+            }}",
+            check
+        );
+
+        rayql_engine::schema::parse(&source)
+            .expect("schema should parse")
+            .to_sql()
+            .map(|sql| sql[0].clone())
+            .map_err(|error| error.to_string())
+    };
+
+    assert!(schema_to_sql("status > 0 and status != status.draft")
+        .unwrap()
+        .contains("CHECK(status > 0 AND status != 0)"));
+    assert!(schema_to_sql("kind = 'page' or kind = kind.article")
+        .unwrap()
+        .contains("CHECK(kind = 'page' OR kind = 'article')"));
+    assert_eq!(
+        schema_to_sql("status = 'draft'").unwrap_err(),
+        "Invalid expression: cannot compare Integer with String at line 8, column 33"
+    );
+    assert_eq!(
+        schema_to_sql("kind > 0").unwrap_err(),
+        "Invalid expression: cannot compare String with Integer at line 8, column 30"
+    );
+}
@@ -0,0 +1,70 @@
+fn item_to_sql(total: &str) -> Result<String, String> {
+    let source = format!(
+        "model item {{
+            id: int primary_key,
+            price: real,
+            qty: int,
+            {},
+            label: str? generated(upper(name), stored),
+            name: str,
+        }}",
+        total
+    );
+
+    rayql_engine::schema::parse(&source)
+        .expect("schema should parse")
+        .to_sql()
+        .map(|sql| sql[0].clone())
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn generated_columns_are_virtual_unless_stored() {
+    assert_eq!(
+        item_to_sql("total: real generated(price * qty)").unwrap(),
+        "CREATE TABLE IF NOT EXISTS item (
+    id INTEGER NOT NULL PRIMARY KEY,
+    price REAL NOT NULL,
+    qty INTEGER NOT NULL,
+    total REAL NOT NULL GENERATED ALWAYS AS (price * qty) VIRTUAL,
+    label TEXT NULL GENERATED ALWAYS AS (upper(name)) STORED,
+    name TEXT NOT NULL
+);"
+    );
+}
+
+#[test]
+fn generated_columns_are_read_only() {
+    let schema = rayql_engine::schema::parse(
+        "model item {
+            id: int primary_key,
+            price: real,
+            total: real generated(price * 2),
+        }",
+    )
+    .expect("schema should parse");
+    let item = schema.get_model("item").unwrap();
+
+    assert!(item.get_field("total").unwrap().is_read_only());
+    assert!(!item.get_field("price").unwrap().is_read_only());
+}
+
+#[test]
+fn invalid_generated_columns_are_rejected() {
+    for (total, error) in [
+        (
+            "total: real generated(total * 2)",
+            "Invalid field 'total' on model 'item': a generated column cannot reference itself at line 5, column 35",
+        ),
+        (
+            "total: real primary_key generated(price * qty)",
+            "Invalid field 'total' on model 'item': a generated column cannot be a primary key at line 5, column 47",
+        ),
+        (
+            "total: str generated(price * qty)",
+            "Invalid expression: cannot compare String with Real at line 5, column 34",
+        ),
+    ] {
+        assert_eq!(item_to_sql(total).unwrap_err(), error, "{}", total);
+    }
+}
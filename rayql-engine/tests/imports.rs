@@ -0,0 +1,130 @@
+use rayql_engine::schema::error::{LoadError, ParseError};
+use std::path::PathBuf;
+
+// Writes the files in a directory of their own and returns the path of the
+// first one.
+fn write_files(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("rayql-{}-{}", test, std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    for (name, code) in files {
+        std::fs::write(directory.join(name), code).unwrap();
+    }
+
+    directory.join(files[0].0).canonicalize().unwrap()
+}
+
+#[test]
+fn files_imported_twice_are_loaded_once() {
+    let path = write_files(
+        "diamond",
+        &[
+            (
+                "schema.rayql",
+                "import 'posts.rayql'
+                import 'comments.rayql'",
+            ),
+            (
+                "posts.rayql",
+                "import 'users.rayql'
+
+                model post {
+                    id: int primary_key,
+                    author_id: int foreign_key(user.id),
+                }",
+            ),
+            (
+                "comments.rayql",
+                "import 'users.rayql'
+
+                model comment {
+                    id: int primary_key,
+                    author_id: int foreign_key(user.id),
+                }",
+            ),
+            (
+                "users.rayql",
+                "model user {
+                    id: int primary_key,
+                }",
+            ),
+        ],
+    );
+
+    let schema = rayql_engine::schema::load(&path).unwrap();
+    let models: Vec<&str> = schema
+        .models
+        .iter()
+        .map(|model| model.name.as_str())
+        .collect();
+
+    assert_eq!(models, ["user", "post", "comment"]);
+    assert_eq!(
+        schema.get_model("user").unwrap().file,
+        Some(path.with_file_name("users.rayql").display().to_string())
+    );
+    assert!(schema.to_sql().is_ok());
+}
+
+#[test]
+fn import_cycles_are_rejected() {
+    let path = write_files(
+        "cycle",
+        &[
+            ("a.rayql", "import 'b.rayql'"),
+            ("b.rayql", "\nimport 'a.rayql'"),
+        ],
+    );
+
+    match rayql_engine::schema::load(&path) {
+        Err(LoadError::ImportCycle {
+            path: file,
+            import,
+            line_number,
+            column,
+        }) => {
+            assert_eq!(file, path.with_file_name("b.rayql").display().to_string());
+            assert_eq!(import, "a.rayql");
+            assert_eq!((line_number, column), (2, 1));
+        }
+        result => panic!("expected an import cycle, got {:?}", result),
+    }
+}
+
+#[test]
+fn identifiers_are_unique_across_files() {
+    let path = write_files(
+        "collision",
+        &[
+            (
+                "schema.rayql",
+                "import 'users.rayql'
+
+                enum user { admin, member }",
+            ),
+            (
+                "users.rayql",
+                "model user {
+                    id: int primary_key,
+                }",
+            ),
+        ],
+    );
+
+    match rayql_engine::schema::load(&path) {
+        Err(LoadError::ParseError {
+            path: file, source, ..
+        }) => {
+            assert_eq!(file, path.display().to_string());
+            assert!(matches!(
+                *source,
+                ParseError::IdentifierAlreadyInUse {
+                    ref identifier,
+                    line_number: 3,
+                    ..
+                } if identifier == "user"
+            ));
+        }
+        result => panic!("expected a parse error, got {:?}", result),
+    }
+}
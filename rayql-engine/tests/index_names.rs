@@ -0,0 +1,54 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .expect("schema should parse")
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn derived_index_names_cannot_collide() {
+    let error = schema_to_sql(
+        "model user {
+            id: int primary_key,
+            email: str,
+            index(email),
+            index(email) unique,
+        }",
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        "Invalid index 'user_email_idx': another index has the same name at line 5, column 13"
+    );
+}
+
+#[test]
+fn partial_indexes_on_the_same_columns_need_explicit_names() {
+    let source = "enum status { active, banned }
+
+        model account {
+            id: int primary_key,
+            email: str,
+            status: status,
+            index(email) unique where status = status.active,
+            index(email) where status = status.banned,
+        }";
+
+    assert_eq!(
+        schema_to_sql(source).unwrap_err(),
+        "Invalid index 'account_email_idx': another index has the same name, name partial indexes explicitly at line 8, column 13"
+    );
+
+    let sql =
+        schema_to_sql(&source.replace("index(email) where", "index banned_email(email) where"))
+            .unwrap();
+
+    assert_eq!(
+        &sql[1..],
+        [
+            "CREATE UNIQUE INDEX IF NOT EXISTS account_email_idx ON account (email) WHERE status = 'active';",
+            "CREATE INDEX IF NOT EXISTS banned_email ON account (email) WHERE status = 'banned';",
+        ]
+    );
+}
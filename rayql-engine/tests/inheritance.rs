@@ -0,0 +1,67 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const SOURCE: &str = "mixin timestamps {
+    created_at: timestamp default(now()),
+}
+
+abstract model base {
+    id: int primary_key,
+    use timestamps,
+    check(id > 0),
+}
+
+model post extends base {
+    title: str,
+}";
+
+#[test]
+fn models_inherit_fields_and_constraints() {
+    assert_eq!(
+        schema_to_sql(SOURCE).unwrap(),
+        ["CREATE TABLE IF NOT EXISTS post (
+    id INTEGER NOT NULL PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    title TEXT NOT NULL,
+    CHECK(id > 0)
+);"]
+    );
+}
+
+#[test]
+fn redeclared_fields_replace_inherited_ones() {
+    let source = SOURCE.replace("    title: str,", "    id: int primary_key auto_increment,");
+
+    assert_eq!(
+        schema_to_sql(&source).unwrap()[0],
+        "CREATE TABLE IF NOT EXISTS post (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK(id > 0)
+);"
+    );
+
+    // inherited constraints are checked against the fields of the child
+    assert_eq!(
+        schema_to_sql(&SOURCE.replace("    title: str,", "    id: str,")).unwrap_err(),
+        "Invalid expression: cannot compare String with Integer at line 8, column 16"
+    );
+}
+
+#[test]
+fn parents_must_be_declared_and_abstract_models_have_no_table() {
+    assert_eq!(
+        schema_to_sql(&SOURCE.replace("extends base", "extends missing")).unwrap_err(),
+        "Model 'missing' is not declared"
+    );
+
+    let source = format!("{}\n\nseed base {{\n    (id: 1),\n}}", SOURCE);
+    assert_eq!(
+        schema_to_sql(&source).unwrap_err(),
+        "Model 'base' is abstract and has no table: line 15, column 1"
+    );
+}
@@ -0,0 +1,86 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .expect("schema should parse")
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn lists_on_both_sides_generate_a_join_table() {
+    let sql = schema_to_sql(
+        "model post {
+            id: int primary_key,
+            tags: tag[],
+            editors: user[] relation(join_table: post_editor),
+        }
+
+        model tag {
+            id: str primary_key,
+            posts: post[],
+        }
+
+        model user {
+            id: int primary_key,
+            edited: post[] relation(join_table: post_editor),
+        }",
+    )
+    .unwrap();
+
+    assert_eq!(
+        &sql[3..],
+        [
+            "CREATE TABLE IF NOT EXISTS post_tag (
+    post_id INTEGER NOT NULL,
+    tag_id TEXT NOT NULL,
+    PRIMARY KEY (post_id, tag_id),
+    FOREIGN KEY (post_id) REFERENCES post(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tag(id) ON DELETE CASCADE
+);",
+            "CREATE TABLE IF NOT EXISTS post_editor (
+    post_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    PRIMARY KEY (post_id, user_id),
+    FOREIGN KEY (post_id) REFERENCES post(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES user(id) ON DELETE CASCADE
+);",
+        ]
+    );
+}
+
+#[test]
+fn models_related_by_several_lists_need_join_table_names() {
+    let source = "model post {
+            id: int primary_key,
+            tags: tag[],
+            labels: tag[],
+        }
+
+        model tag {
+            id: int primary_key,
+            posts: post[],
+            labelled: post[],
+        }";
+
+    assert_eq!(
+        schema_to_sql(source).unwrap_err(),
+        "Invalid relation 'tags' on model 'post': 'post' and 'tag' are related by more than one list, use relation(join_table: <name>) on both sides at line 3, column 19"
+    );
+
+    let sql = schema_to_sql(
+        &source
+            .replace("tags: tag[]", "tags: tag[] relation(join_table: post_tag)")
+            .replace(
+                "posts: post[]",
+                "posts: post[] relation(join_table: post_tag)",
+            )
+            .replace("labels: tag[]", "labels: tag[] relation(join_table: label)")
+            .replace(
+                "labelled: post[]",
+                "labelled: post[] relation(join_table: label)",
+            ),
+    )
+    .unwrap();
+
+    assert_eq!(sql.len(), 4);
+    assert!(sql[3].starts_with("CREATE TABLE IF NOT EXISTS label (\n    post_id"));
+}
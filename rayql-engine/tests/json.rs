@@ -0,0 +1,164 @@
+use rayql_engine::{
+    sql::json::{check_shape, parse, JsonValue},
+    types::DataType,
+};
+
+fn shape_schema() -> rayql_engine::Schema {
+    rayql_engine::schema::parse(
+        "enum role { admin, member }
+        enum level: int { low, high }
+
+        struct address {
+            lines: str[],
+            zip: str?,
+        }
+
+        struct profile {
+            role: role,
+            level: level?,
+            balance: decimal(6, 2)?,
+            address: json<address>?,
+        }",
+    )
+    .expect("schema should parse")
+}
+
+fn check_profile(json: &str) -> Result<(), String> {
+    check_shape(
+        &shape_schema(),
+        &parse(json).expect("json should parse"),
+        &DataType::Json(Some("profile".to_string())),
+    )
+}
+
+#[test]
+fn documents_are_parsed() {
+    assert_eq!(
+        parse(r#" { "a": [1, -2.5e3, true, null], "b": {} } "#),
+        Ok(JsonValue::Object(vec![
+            (
+                "a".to_string(),
+                JsonValue::Array(vec![
+                    JsonValue::Number(1.0),
+                    JsonValue::Number(-2500.0),
+                    JsonValue::Boolean(true),
+                    JsonValue::Null,
+                ])
+            ),
+            ("b".to_string(), JsonValue::Object(vec![])),
+        ]))
+    );
+    assert_eq!(
+        parse("[1] 2"),
+        Err("unexpected character '2' after the document".to_string())
+    );
+    assert_eq!(parse("nul"), Err("unexpected word 'nul'".to_string()));
+}
+
+#[test]
+fn numbers_follow_the_json_grammar() {
+    for (number, value) in [("0", 0.0), ("-0.5", -0.5), ("10", 10.0), ("1e2", 100.0)] {
+        assert_eq!(parse(number), Ok(JsonValue::Number(value)), "{}", number);
+    }
+
+    for number in ["01", "-01", "1.", "1.e5", "-", "--1", "1e", "+1", "1.2.3"] {
+        assert!(parse(number).is_err(), "{}", number);
+    }
+    assert_eq!(parse("01"), Err("invalid number '01'".to_string()));
+}
+
+#[test]
+fn strings_are_unescaped() {
+    assert_eq!(
+        parse(r#""a\"b\\c\/\n\t\u00e9\u20AC""#),
+        Ok(JsonValue::String("a\"b\\c/\n\té€".to_string()))
+    );
+    assert_eq!(
+        parse(r#""\u12""#),
+        Err("invalid unicode escape '\\u12\"'".to_string())
+    );
+    assert_eq!(
+        parse(r#""\uzzzz""#),
+        Err("invalid unicode escape '\\uzzzz'".to_string())
+    );
+    assert_eq!(parse(r#""\x""#), Err("invalid escape '\\x'".to_string()));
+    assert_eq!(
+        parse("\"a\nb\""),
+        Err("control characters must be escaped in strings".to_string())
+    );
+    assert_eq!(parse(r#""abc"#), Err("unterminated string".to_string()));
+}
+
+#[test]
+fn duplicate_keys_are_rejected() {
+    assert_eq!(
+        parse(r#"{"a": 1, "b": 2, "a": 3}"#),
+        Err("duplicate key 'a'".to_string())
+    );
+}
+
+#[test]
+fn shapes_require_their_fields() {
+    assert_eq!(check_profile("{}"), Err("missing field 'role'".to_string()));
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "address": {"zip": "1000"}}"#),
+        Err("address: missing field 'lines'".to_string())
+    );
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "age": 3}"#),
+        Err("'age' is not a field of 'profile'".to_string())
+    );
+}
+
+#[test]
+fn optional_fields_can_be_missing_or_null() {
+    assert_eq!(check_profile(r#"{"role": "member"}"#), Ok(()));
+    assert_eq!(
+        check_profile(r#"{"role": "member", "level": null, "address": null}"#),
+        Ok(())
+    );
+    assert_eq!(
+        check_profile(r#"{"role": "member", "address": {"lines": ["a"]}}"#),
+        Ok(())
+    );
+    assert_eq!(
+        check_profile(r#"{"role": "member", "address": {"lines": ["a", 1]}}"#),
+        Err("address: lines: expected a string, got a number".to_string())
+    );
+}
+
+#[test]
+fn enum_members_must_be_variants() {
+    assert_eq!(check_profile(r#"{"role": "admin", "level": 1}"#), Ok(()));
+    assert_eq!(
+        check_profile(r#"{"role": "owner"}"#),
+        Err("role: not a variant of enum 'role'".to_string())
+    );
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "level": 2}"#),
+        Err("level: not a variant of enum 'level'".to_string())
+    );
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "level": "low"}"#),
+        Err("level: not a variant of enum 'level'".to_string())
+    );
+}
+
+#[test]
+fn decimal_members_must_fit() {
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "balance": "1234.50"}"#),
+        Ok(())
+    );
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "balance": 12.5}"#),
+        Ok(())
+    );
+    assert!(check_profile(r#"{"role": "admin", "balance": "12345.5"}"#).is_err());
+    assert!(check_profile(r#"{"role": "admin", "balance": 1.234}"#).is_err());
+    assert!(check_profile(r#"{"role": "admin", "balance": "abc"}"#).is_err());
+    assert_eq!(
+        check_profile(r#"{"role": "admin", "balance": true}"#),
+        Err("balance: expected a decimal, got a boolean".to_string())
+    );
+}
@@ -0,0 +1,83 @@
+use rayql_engine::schema::error::{LoadError, ParseError};
+
+const SOURCE: &str = "mixin timestamps {
+    created_at: timestamp default(now()),
+    updated_at: timestamp?,
+}
+
+model post {
+    id: int primary_key,
+    use timestamps,
+    title: str,
+}";
+
+#[test]
+fn mixin_fields_are_spliced_where_they_are_used() {
+    let sql = rayql_engine::schema::parse(SOURCE)
+        .expect("schema should parse")
+        .to_sql()
+        .expect("schema should render");
+
+    assert_eq!(
+        sql,
+        ["CREATE TABLE IF NOT EXISTS post (
+    id INTEGER NOT NULL PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NULL,
+    title TEXT NOT NULL
+);"]
+    );
+}
+
+#[test]
+fn mixin_fields_cannot_clash_with_the_model() {
+    let source = SOURCE.replace("    title: str,", "    updated_at: str,");
+    let error = rayql_engine::schema::parse(&source).unwrap_err();
+
+    assert_eq!(
+        rayql_engine::error::pretty_error_message(&error, &source),
+        "Field 'updated_at' already exists on model 'post' (declared at line 9, column 5), mixin 'timestamps' used at line 8, column 9 declares it again at line 3, column 5."
+    );
+}
+
+#[test]
+fn clashes_point_at_the_file_declaring_the_mixin() {
+    let directory = std::env::temp_dir().join(format!("rayql-mixins-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(
+        directory.join("timestamps.rayql"),
+        "mixin timestamps {\n    id: int,\n}",
+    )
+    .unwrap();
+    std::fs::write(
+        directory.join("schema.rayql"),
+        "import 'timestamps.rayql'\n\nmodel post {\n    id: int primary_key,\n    use timestamps,\n}",
+    )
+    .unwrap();
+    let path = directory.join("schema.rayql").canonicalize().unwrap();
+
+    match rayql_engine::schema::load(&path) {
+        Err(LoadError::ParseError {
+            path: file, source, ..
+        }) => {
+            assert_eq!(file, path.display().to_string());
+            match *source {
+                ParseError::MixinFieldAlreadyExistsOnModel(clash) => {
+                    assert_eq!((clash.line_number, clash.other_line_number), (5, 4));
+                    assert_eq!(clash.other_file, None);
+                    assert_eq!(clash.mixin_line_number, 2);
+                    assert_eq!(
+                        clash.mixin_file,
+                        Some(
+                            path.with_file_name("timestamps.rayql")
+                                .display()
+                                .to_string()
+                        )
+                    );
+                }
+                source => panic!("expected a mixin field clash, got {:?}", source),
+            }
+        }
+        result => panic!("expected a parse error, got {:?}", result),
+    }
+}
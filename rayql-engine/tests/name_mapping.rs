@@ -0,0 +1,69 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const SOURCE: &str = "model user {
+    map(\"users\"),
+    id: int primary_key auto_increment map(\"user_id\"),
+    email: str unique map(\"email_address\") check(length(email) > 3),
+    index(email),
+}
+
+model post {
+    map(\"blog posts\"),
+    id: int primary_key,
+    author_id: int foreign_key(user.id) map(\"author\"),
+}
+
+view author {
+    user.email,
+    post.id,
+    join(post.author_id, user.id),
+}";
+
+#[test]
+fn mapped_names_are_used_in_the_generated_sql() {
+    assert_eq!(
+        schema_to_sql(SOURCE).unwrap(),
+        [
+            "CREATE TABLE IF NOT EXISTS users (
+    user_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    email_address TEXT NOT NULL UNIQUE CHECK(length(email_address) > 3)
+);",
+            "CREATE TABLE IF NOT EXISTS \"blog posts\" (
+    id INTEGER NOT NULL PRIMARY KEY,
+    author INTEGER NOT NULL,
+    FOREIGN KEY (author) REFERENCES users(user_id)
+);",
+            "CREATE INDEX IF NOT EXISTS user_email_idx ON users (email_address);",
+            "CREATE VIEW IF NOT EXISTS author AS
+SELECT users.email_address AS email, \"blog posts\".id
+FROM users
+JOIN \"blog posts\" ON \"blog posts\".author = users.user_id;",
+        ]
+    );
+}
+
+#[test]
+fn schema_names_are_kept() {
+    let schema = rayql_engine::schema::parse(SOURCE).expect("schema should parse");
+    let user = schema.get_model("user").unwrap();
+
+    assert_eq!(user.table_name(), "users");
+    assert_eq!(
+        user.get_field("email").unwrap().column_name(),
+        "email_address"
+    );
+    assert!(schema.get_model("users").is_none());
+}
+
+#[test]
+fn mapped_columns_cannot_collide() {
+    assert_eq!(
+        schema_to_sql(&SOURCE.replace("map(\"author\")", "map(\"id\")")).unwrap_err(),
+        "Invalid field 'author_id' on model 'post': column 'id' is already used by field 'id' at line 11, column 5"
+    );
+}
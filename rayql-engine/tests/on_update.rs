@@ -0,0 +1,56 @@
+fn schema_to_sql(source: &str) -> Vec<String> {
+    rayql_engine::schema::parse(source)
+        .expect("schema should parse")
+        .to_sql()
+        .expect("schema should render")
+}
+
+const POST: &str = "model post {
+    id: int primary_key,
+    title: str,
+    updated_at: timestamp default(now()) on_update(now()),
+}";
+
+#[test]
+fn on_update_fields_are_refreshed_by_a_trigger() {
+    assert_eq!(
+        schema_to_sql(POST)[1],
+        "CREATE TRIGGER IF NOT EXISTS post_on_update
+AFTER UPDATE ON post
+FOR EACH ROW
+WHEN NEW.updated_at IS OLD.updated_at
+BEGIN
+    UPDATE post SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;"
+    );
+
+    let sql = schema_to_sql(&POST.replace("    id: int primary_key,\n", ""));
+    assert!(sql[1].contains("WHERE rowid = NEW.rowid;"));
+}
+
+#[test]
+fn explicit_updates_are_kept() {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(&schema_to_sql(POST).join("\n"))
+        .unwrap();
+
+    let updated_at = || -> String {
+        connection
+            .query_row("SELECT updated_at FROM post", [], |row| row.get(0))
+            .unwrap()
+    };
+
+    connection
+        .execute_batch(
+            "INSERT INTO post (id, title, updated_at) VALUES (1, 'a', '2000-01-01 00:00:00');
+            UPDATE post SET title = 'b', updated_at = '2001-01-01 00:00:00';",
+        )
+        .unwrap();
+    assert_eq!(updated_at(), "2001-01-01 00:00:00");
+
+    connection
+        .execute_batch("UPDATE post SET title = 'c';")
+        .unwrap();
+    assert_ne!(updated_at(), "2001-01-01 00:00:00");
+}
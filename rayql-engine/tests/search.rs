@@ -0,0 +1,119 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const POST: &str = "model post {
+    id: int primary_key,
+    title: str,
+    body: str?,
+    views: int,
+}
+
+search post(title, body)";
+
+#[test]
+fn searches_generate_an_fts5_table_and_triggers() {
+    let sql = schema_to_sql(POST).unwrap();
+
+    assert_eq!(
+        sql[1],
+        "CREATE VIRTUAL TABLE IF NOT EXISTS post_search USING fts5(title, body, content='post', content_rowid='id');"
+    );
+    assert_eq!(
+        sql[4],
+        "CREATE TRIGGER IF NOT EXISTS post_search_update
+AFTER UPDATE ON post
+FOR EACH ROW
+BEGIN
+    INSERT INTO post_search(post_search, rowid, title, body) VALUES ('delete', OLD.id, OLD.title, OLD.body);
+    INSERT INTO post_search(rowid, title, body) VALUES (NEW.id, NEW.title, NEW.body);
+END;"
+    );
+}
+
+#[test]
+fn triggers_keep_the_index_in_sync() {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(&schema_to_sql(POST).unwrap().join("\n"))
+        .unwrap();
+
+    let matches = |query: &str| -> Vec<i64> {
+        let mut statement = connection
+            .prepare("SELECT rowid FROM post_search WHERE post_search MATCH ?1 ORDER BY rowid")
+            .unwrap();
+        statement
+            .query_map([query], |row| row.get(0))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+    };
+
+    connection
+        .execute_batch(
+            "INSERT INTO post VALUES (1, 'Hello world', NULL, 0);
+            INSERT INTO post VALUES (2, 'Other', 'hello again', 0);",
+        )
+        .unwrap();
+    assert_eq!(matches("hello"), [1, 2]);
+
+    connection
+        .execute_batch("UPDATE post SET title = 'Goodbye' WHERE id = 1;")
+        .unwrap();
+    assert_eq!(matches("hello"), [2]);
+    assert_eq!(matches("goodbye"), [1]);
+
+    connection
+        .execute_batch("DELETE FROM post WHERE id = 2;")
+        .unwrap();
+    assert_eq!(matches("hello"), Vec::<i64>::new());
+}
+
+#[test]
+fn only_text_fields_can_be_searched() {
+    for (fields, error) in [
+        (
+            "search post(title, views)",
+            "Invalid search for model 'post': only text fields can be searched, 'views' is Integer at line 8, column 20",
+        ),
+        (
+            "search post(title, title)",
+            "Invalid search for model 'post': 'title' is listed more than once at line 8, column 20",
+        ),
+    ] {
+        assert_eq!(
+            schema_to_sql(&POST.replace("search post(title, body)", fields)).unwrap_err(),
+            error,
+            "{}",
+            fields
+        );
+    }
+}
+
+#[test]
+fn search_tables_cannot_reuse_a_name() {
+    for (declaration, used_by) in [
+        (
+            "model post_search {\n    id: int primary_key,\n}",
+            "a table",
+        ),
+        ("view post_search {\n    post.id,\n}", "a view"),
+        ("index post_search on post(title)", "an index"),
+    ] {
+        let source = POST.replace("search post", &format!("{}\n\nsearch post", declaration));
+        let line_number = source.lines().count();
+
+        assert_eq!(
+            schema_to_sql(&source).unwrap_err(),
+            format!(
+                "Invalid search for model 'post': the search table 'post_search' has the same name as {} at line {}, column 1",
+                used_by, line_number
+            ),
+            "{}",
+            declaration
+        );
+    }
+}
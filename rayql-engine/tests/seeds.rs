@@ -0,0 +1,69 @@
+fn schema_to_sql(source: &str) -> Result<String, String> {
+    rayql_engine::schema::parse(source)
+        .expect("schema should parse")
+        .to_sql()
+        .map(|statements| statements.join("\n"))
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn seeds_can_be_applied_twice() {
+    let sql = schema_to_sql(
+        "model plan {
+            id: int primary_key auto_increment,
+            code: str unique,
+            name: str,
+        }
+
+        model post {
+            id: int primary_key,
+            title: str,
+        }
+
+        seed plan upsert(code) {
+            (code: 'free', name: 'Free'),
+        }
+
+        seed plan {
+            (code: 'pro', name: 'Pro'),
+        }
+
+        seed post {
+            (id: 1, title: 'Hello'),
+        }",
+    )
+    .unwrap();
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(&sql).unwrap();
+    connection.execute_batch(&sql).unwrap();
+
+    for (table, count) in [("plan", 2), ("post", 1)] {
+        let rows: i64 = connection
+            .query_row(&format!("SELECT count(*) FROM \"{}\"", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(rows, count, "{}", table);
+    }
+}
+
+#[test]
+fn rows_without_a_key_need_an_upsert() {
+    let error = schema_to_sql(
+        "model post {
+            id: int primary_key auto_increment,
+            title: str,
+        }
+
+        seed post {
+            (title: 'Hello'),
+        }",
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        "Invalid seed for model 'post': the row sets no primary key or unique field, set one or use upsert(<field>) at line 7, column 13"
+    );
+}
@@ -0,0 +1,98 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const ACCOUNT: &str = "model account soft_delete {
+    id: int primary_key auto_increment,
+    email: str unique,
+    name: str,
+}";
+
+#[test]
+fn unique_fields_ignore_deleted_rows() {
+    let sql = schema_to_sql(ACCOUNT).unwrap();
+
+    assert_eq!(
+        sql,
+        [
+            "CREATE TABLE IF NOT EXISTS account (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    email TEXT NOT NULL,
+    name TEXT NOT NULL,
+    deleted_at TIMESTAMP NULL
+);",
+            "CREATE UNIQUE INDEX IF NOT EXISTS account_email_unique ON account (email) WHERE deleted_at IS NULL;",
+            "CREATE VIEW IF NOT EXISTS account_active AS
+SELECT *
+FROM account
+WHERE deleted_at IS NULL;",
+        ]
+    );
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(&sql.join("\n")).unwrap();
+    connection
+        .execute_batch(
+            "INSERT INTO account (email, name) VALUES ('ray@example.com', 'Ray');
+            UPDATE account SET deleted_at = CURRENT_TIMESTAMP;
+            INSERT INTO account (email, name) VALUES ('ray@example.com', 'Ray');",
+        )
+        .unwrap();
+    assert!(connection
+        .execute_batch("INSERT INTO account (email, name) VALUES ('ray@example.com', 'Ray');")
+        .is_err());
+
+    let active: i64 = connection
+        .query_row("SELECT count(*) FROM account_active", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(active, 1);
+}
+
+#[test]
+fn deleted_at_must_be_an_optional_timestamp() {
+    let schema = rayql_engine::schema::parse(ACCOUNT).expect("schema should parse");
+    assert!(schema.get_model("account").unwrap().is_soft_delete());
+
+    assert_eq!(
+        schema_to_sql(&ACCOUNT.replace("name: str", "deleted_at: str")).unwrap_err(),
+        "Invalid option 'soft_delete' on model 'account': 'deleted_at' must be an optional timestamp, got String at line 4, column 5"
+    );
+}
+
+#[test]
+fn referenced_unique_fields_keep_their_unique_column() {
+    let source = format!(
+        "{}\n\nmodel invite {{\n    id: int primary_key,\n    email: str foreign_key(account.email),\n}}",
+        ACCOUNT
+    );
+    let sql = schema_to_sql(&source).unwrap();
+
+    assert_eq!(
+        sql[0],
+        "CREATE TABLE IF NOT EXISTS account (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    email TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    deleted_at TIMESTAMP NULL
+);"
+    );
+    assert!(!sql
+        .iter()
+        .any(|statement| statement.contains("account_email_unique")));
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(&sql.join("\n")).unwrap();
+    connection
+        .execute_batch(
+            "PRAGMA foreign_keys = ON;
+            INSERT INTO account (email, name) VALUES ('ray@example.com', 'Ray');
+            INSERT INTO invite VALUES (1, 'ray@example.com');",
+        )
+        .unwrap();
+    assert!(connection
+        .execute_batch("INSERT INTO invite VALUES (2, 'other@example.com');")
+        .is_err());
+}
@@ -0,0 +1,178 @@
+use rayql_engine::sql::to_sql::{is_reserved_word, quote_identifier, quote_string};
+
+fn schema_to_sql(source: &str) -> Vec<String> {
+    rayql_engine::schema::parse(source)
+        .expect("schema should parse")
+        .to_sql()
+        .expect("schema should render")
+}
+
+#[test]
+fn plain_identifiers_are_not_quoted() {
+    for identifier in ["user", "user_id", "_private", "Post2", "a", "rowid", "oid"] {
+        assert_eq!(quote_identifier(identifier), identifier);
+    }
+}
+
+#[test]
+fn reserved_words_are_quoted_regardless_of_case() {
+    for (identifier, quoted) in [
+        ("order", "\"order\""),
+        ("group", "\"group\""),
+        ("select", "\"select\""),
+        ("Table", "\"Table\""),
+        ("WHERE", "\"WHERE\""),
+        ("current_timestamp", "\"current_timestamp\""),
+        ("without", "\"without\""),
+    ] {
+        assert_eq!(quote_identifier(identifier), quoted);
+    }
+}
+
+#[test]
+fn identifiers_that_are_not_plain_are_quoted() {
+    for (identifier, quoted) in [
+        ("line item", "\"line item\""),
+        ("2fa", "\"2fa\""),
+        ("e-mail", "\"e-mail\""),
+        ("prix_€", "\"prix_€\""),
+        ("", "\"\""),
+        ("weird\"col", "\"weird\"\"col\""),
+        ("\"", "\"\"\"\""),
+        ("it's", "\"it's\""),
+    ] {
+        assert_eq!(quote_identifier(identifier), quoted);
+    }
+}
+
+#[test]
+fn every_sqlite_keyword_is_reserved() {
+    for keyword in [
+        "ABORT",
+        "ACTION",
+        "AUTOINCREMENT",
+        "CASCADE",
+        "CONFLICT",
+        "DEFERRABLE",
+        "EXCLUDE",
+        "FILTER",
+        "GENERATED",
+        "GROUPS",
+        "INDEXED",
+        "MATERIALIZED",
+        "NOTHING",
+        "NULLS",
+        "OTHERS",
+        "RETURNING",
+        "TIES",
+        "UNBOUNDED",
+        "VIRTUAL",
+        "WINDOW",
+    ] {
+        assert!(is_reserved_word(keyword), "{} should be reserved", keyword);
+    }
+
+    for word in ["user", "post", "rowid", "text", "integer", "date", "json"] {
+        assert!(!is_reserved_word(word), "{} should not be reserved", word);
+    }
+}
+
+#[test]
+fn string_literals_double_embedded_quotes() {
+    for (string, quoted) in [
+        ("", "''"),
+        ("plain", "'plain'"),
+        ("it's", "'it''s'"),
+        ("''", "''''''"),
+        ("say \"hi\"", "'say \"hi\"'"),
+        ("back\\slash", "'back\\slash'"),
+        ("line\nbreak", "'line\nbreak'"),
+        ("-- not a comment", "'-- not a comment'"),
+        ("'; DROP TABLE user; --", "'''; DROP TABLE user; --'"),
+    ] {
+        assert_eq!(quote_string(string), quoted);
+    }
+}
+
+#[test]
+fn tables_and_columns_named_after_keywords_are_quoted() {
+    let sql = schema_to_sql(
+        "model order {
+            id: int primary_key auto_increment,
+            group: str,
+            check(group != ''),
+            index order_group(group),
+        }",
+    );
+
+    assert_eq!(
+        sql,
+        vec![
+            "CREATE TABLE IF NOT EXISTS \"order\" (\n    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,\n    \"group\" TEXT NOT NULL,\n    CHECK(\"group\" != '')\n);",
+            "CREATE INDEX IF NOT EXISTS order_group ON \"order\" (\"group\");",
+        ]
+    );
+}
+
+#[test]
+fn defaults_and_enum_values_are_escaped() {
+    let sql = schema_to_sql(
+        "enum quote { single = 'it\\'s' }
+
+        model note {
+            id: int primary_key,
+            body: str default('it\\'s'),
+            quote: quote,
+        }",
+    );
+
+    assert_eq!(
+        sql,
+        vec![
+            "CREATE TABLE IF NOT EXISTS note (\n    id INTEGER NOT NULL PRIMARY KEY,\n    body TEXT NOT NULL DEFAULT 'it''s',\n    quote TEXT NOT NULL CHECK(quote IN ('it''s'))\n);",
+        ]
+    );
+}
+
+#[test]
+fn mapped_names_are_quoted_in_references() {
+    let sql = schema_to_sql(
+        "model user {
+            map(\"user table\"),
+            id: int primary_key map(\"key\"),
+        }
+
+        model post {
+            id: int primary_key,
+            author_id: int foreign_key(user.id),
+        }",
+    );
+
+    assert_eq!(
+        sql[1],
+        "CREATE TABLE IF NOT EXISTS post (\n    id INTEGER NOT NULL PRIMARY KEY,\n    author_id INTEGER NOT NULL,\n    FOREIGN KEY (author_id) REFERENCES \"user table\"(\"key\")\n);"
+    );
+}
+
+#[test]
+fn triggers_and_views_quote_their_names() {
+    let sql = schema_to_sql(
+        "model order {
+            id: int primary_key,
+            when: timestamp default(now()) on_update(now()),
+        }
+
+        view table {
+            order.when,
+        }",
+    );
+
+    assert_eq!(
+        sql[1],
+        "CREATE TRIGGER IF NOT EXISTS order_on_update\nAFTER UPDATE ON \"order\"\nFOR EACH ROW\nWHEN NEW.\"when\" IS OLD.\"when\"\nBEGIN\n    UPDATE \"order\" SET \"when\" = CURRENT_TIMESTAMP WHERE id = NEW.id;\nEND;"
+    );
+    assert_eq!(
+        sql[2],
+        "CREATE VIEW IF NOT EXISTS \"table\" AS\nSELECT \"order\".\"when\"\nFROM \"order\";"
+    );
+}
@@ -0,0 +1,48 @@
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const FLAG: &str = "model flag strict without_rowid {
+    id: int primary_key,
+    on: bool,
+    at: timestamp,
+}";
+
+#[test]
+fn strict_tables_only_use_strict_types() {
+    let sql = schema_to_sql(FLAG).unwrap();
+
+    assert_eq!(
+        sql,
+        ["CREATE TABLE IF NOT EXISTS flag (
+    id INTEGER NOT NULL PRIMARY KEY,
+    \"on\" INTEGER NOT NULL,
+    at TEXT NOT NULL
+) STRICT, WITHOUT ROWID;"]
+    );
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(&sql[0]).unwrap();
+    connection
+        .execute_batch("INSERT INTO flag VALUES (1, 1, '2024-01-01 00:00:00');")
+        .unwrap();
+    assert!(connection
+        .execute_batch("INSERT INTO flag VALUES (2, 'yes', '2024-01-01 00:00:00');")
+        .is_err());
+}
+
+#[test]
+fn tables_without_rowid_need_a_primary_key() {
+    assert_eq!(
+        schema_to_sql(&FLAG.replace("id: int primary_key,", "id: int,")).unwrap_err(),
+        "Invalid option 'without_rowid' on model 'flag': a primary key is required at line 1, column 19"
+    );
+    assert_eq!(
+        schema_to_sql(&FLAG.replace("int primary_key,", "int primary_key auto_increment,"))
+            .unwrap_err(),
+        "Invalid option 'without_rowid' on model 'flag': 'id' cannot use auto_increment at line 2, column 5"
+    );
+}
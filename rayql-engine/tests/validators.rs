@@ -0,0 +1,98 @@
+use rayql_engine::{schema::Validation, Value};
+
+fn schema_to_sql(source: &str) -> Result<Vec<String>, String> {
+    rayql_engine::schema::parse(source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map_err(|error| error.to_string())
+}
+
+const ACCOUNT: &str = "model account {
+    id: int primary_key auto_increment,
+    handle: str pattern('[a-z0-9_]*') length(3, 20),
+    email: str email(),
+    website: str? url(),
+    age: int between(13, 130),
+}";
+
+#[test]
+fn validators_are_exposed_to_clients() {
+    let schema = rayql_engine::schema::parse(ACCOUNT).expect("schema should parse");
+    let account = schema.get_model("account").unwrap();
+    let validations = |field: &str| account.get_field(field).unwrap().validations().unwrap();
+
+    assert_eq!(
+        validations("handle"),
+        [
+            Validation::Pattern("[a-z0-9_]*".to_string()),
+            Validation::Length {
+                min: Some(3),
+                max: Some(20)
+            },
+        ]
+    );
+    assert_eq!(validations("email"), [Validation::Email]);
+    assert_eq!(
+        validations("age"),
+        [Validation::Between(Value::Integer(13), Value::Integer(130))]
+    );
+}
+
+#[test]
+fn validators_are_enforced_by_the_database() {
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(&schema_to_sql(ACCOUNT).unwrap().join("\n"))
+        .unwrap();
+
+    let insert = |handle: &str, email: &str, website: Option<&str>, age: i64| {
+        connection
+            .execute(
+                "INSERT INTO account (handle, email, website, age) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![handle, email, website, age],
+            )
+            .is_ok()
+    };
+
+    assert!(insert("ray_01", "ray@example.com", None, 30));
+    assert!(insert(
+        "ray_02",
+        "ray@example.com",
+        Some("https://ray.dev"),
+        13
+    ));
+    assert!(!insert("Ray", "ray@example.com", None, 30));
+    assert!(!insert("ra", "ray@example.com", None, 30));
+    assert!(!insert("ray_03", "ray@example", None, 30));
+    assert!(!insert("ray_04", "ray@@example.com", None, 30));
+    assert!(!insert(
+        "ray_05",
+        "ray@example.com",
+        Some("ftp://ray.dev"),
+        30
+    ));
+    assert!(!insert("ray_06", "ray@example.com", None, 12));
+}
+
+#[test]
+fn invalid_validators_are_rejected() {
+    for (from, to, error) in [
+        (
+            "int between(13, 130)",
+            "int email()",
+            "Function error: Invalid argument: email can only be used on str fields, 'age' is Integer at line 6, column 14",
+        ),
+        (
+            "length(3, 20)",
+            "length(20, 3)",
+            "Function error: Invalid argument: the minimum length is greater than the maximum length at line 3, column 39",
+        ),
+    ] {
+        assert_eq!(
+            schema_to_sql(&ACCOUNT.replace(from, to)).unwrap_err(),
+            error,
+            "{}",
+            to
+        );
+    }
+}
@@ -0,0 +1,90 @@
+fn view_to_sql(view: &str) -> Result<String, String> {
+    let source = format!(
+        "model user {{
+            id: int primary_key,
+            username: str,
+        }}
+
+        model post {{
+            id: int primary_key,
+            title: str,
+            author_id: int? foreign_key(user.id),
+        }}
+
+        {}",
+        view
+    );
+
+    rayql_engine::schema::parse(&source)
+        .map_err(|error| error.to_string())?
+        .to_sql()
+        .map(|sql| sql[2].clone())
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn views_select_and_join_models() {
+    assert_eq!(
+        view_to_sql(
+            "view post_author {
+                ## the post
+                post.id,
+                post.title,
+                author_name: user.username,
+                left_join(post.author_id, user.id),
+            }"
+        )
+        .unwrap(),
+        "CREATE VIEW IF NOT EXISTS post_author AS
+SELECT post.id, post.title, user.username AS author_name
+FROM post
+LEFT JOIN user ON post.author_id = user.id;"
+    );
+}
+
+#[test]
+fn views_are_validated_against_the_schema() {
+    for (view, error) in [
+        (
+            "view post_author {
+                post.id,
+                user.username,
+            }",
+            "Invalid view 'post_author': model 'user' is not joined in the view at line 14, column 17",
+        ),
+        (
+            "view post_author {
+                post.id,
+                post.body,
+            }",
+            "Field 'body' does not exists on model 'post': line 14, column 17",
+        ),
+        (
+            "view post_author {
+                post.id,
+                user.id,
+                join(post.author_id, user.id),
+            }",
+            "Column with name 'id' already exists on view 'post_author'",
+        ),
+    ] {
+        assert_eq!(view_to_sql(view).unwrap_err(), error, "{}", view);
+    }
+}
+
+#[test]
+fn view_is_not_reserved() {
+    let schema = rayql_engine::schema::parse(
+        "model view {
+            id: int primary_key,
+            view: str,
+        }",
+    )
+    .expect("schema should parse");
+
+    assert!(schema
+        .get_model("view")
+        .unwrap()
+        .get_field("view")
+        .is_some());
+}
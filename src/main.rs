@@ -22,7 +22,7 @@ pub fn print_schema() {
     let current_dir = std::env::current_dir().expect("Failed to read current dir.");
     let file_path = current_dir.join("schema.rayql");
 
-    let code = match std::fs::read_to_string(file_path) {
+    let code = match std::fs::read_to_string(&file_path) {
         Ok(content) => content,
         Err(e) => {
             eprintln!("Error reading file: {}", e);
@@ -30,10 +30,10 @@ pub fn print_schema() {
         }
     };
 
-    let schema = match rayql_engine::Schema::parse(&code) {
+    let schema = match rayql_engine::Schema::parse_file(&file_path, &code) {
         Ok(schema) => schema,
         Err(err) => {
-            eprintln!("{}", rayql_engine::error::pretty_error_message(&err, &code));
+            eprintln!("{}", rayql_engine::error::pretty_load_error_message(&err));
             std::process::exit(1);
         }
     };